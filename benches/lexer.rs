@@ -0,0 +1,123 @@
+//! Measures the lexer's throughput on large files.
+//!
+//! Run with `cargo bench`. Files passed as arguments, like
+//! `cargo bench -- path/to/file.luau`, are measured instead of the bundled
+//! samples.
+
+use luau_lexer::prelude::{Lexer, TokenType};
+use std::{
+    env, fs,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Typical code, repeated to build the large samples.
+const CODE: &str = r#"
+local Players = require(script.Parent.Players)
+local Signal = require(script.Parent.Signal)
+
+export type Options = {
+    name: string,
+    retries: number?,
+    callback: ((value: number) -> ())?,
+}
+
+local Module = {}
+Module.__index = Module
+
+function Module.new(options: Options): Module
+    local self = setmetatable({}, Module)
+    self.name = options.name
+    self.retries = options.retries or 3
+    self.changed = Signal.new()
+
+    return self
+end
+
+function Module:step(delta: number)
+    for index, player in Players:GetPlayers() do
+        if player.Name == self.name and index % 2 == 0 then
+            self.changed:Fire(`{player.Name} moved {delta * 1.5}`)
+        elseif index > 0x10 then
+            continue
+        else
+            self.retries -= 1
+        end
+    end
+end
+"#;
+
+/// Comments, to measure skipping trivia.
+const COMMENTS: &str = r"
+-- A single line comment which is long enough to matter, explaining the code.
+--[[
+    A multi-line comment, like the documentation of a module, which goes on
+    for a few lines without any code at all.
+]]
+--[==[ a comment with ]] inside of it ]==]
+";
+
+/// Strings, to measure skipping to their ends.
+const STRINGS: &str = r#"
+local a = "a double quoted string with \"escapes\" and \\ backslashes"
+local b = 'a single quoted string which is quite a bit longer than the others'
+local c = [[
+    a multi-line string with some text in it
+    and some more text on another line
+]]
+local d = `an interpolated string {a} with {b .. c}`
+"#;
+
+/// The amount of time to spend lexing each sample.
+const DURATION: Duration = Duration::from_secs(2);
+
+fn main() {
+    let paths = env::args()
+        .skip(1)
+        .filter(|argument| !argument.starts_with("--"))
+        .collect::<Vec<_>>();
+
+    if paths.is_empty() {
+        for (name, sample) in [
+            ("code", CODE),
+            ("comments", COMMENTS),
+            ("strings", STRINGS),
+            ("mixed", &[CODE, COMMENTS, STRINGS].concat()),
+        ] {
+            bench(name, &sample.repeat(4 * 1024 * 1024 / sample.len()));
+        }
+    } else {
+        for path in paths {
+            match fs::read_to_string(&path) {
+                Ok(code) => bench(&path, &code),
+                Err(error) => eprintln!("{path}: {error}"),
+            }
+        }
+    }
+}
+
+/// Lex the passed code for [`DURATION`] and print the throughput.
+fn bench(name: &str, code: &str) {
+    let mut runs = 0_u32;
+    let mut tokens = 0;
+    let start = Instant::now();
+
+    while start.elapsed() < DURATION {
+        let mut lexer = Lexer::from_input(black_box(code));
+        tokens = 0;
+
+        while lexer.next_token() != TokenType::EndOfFile {
+            tokens += 1;
+        }
+
+        runs += 1;
+    }
+
+    let elapsed = start.elapsed() / runs;
+    let throughput = code.len() as f64 / elapsed.as_secs_f64() / (1024.0 * 1024.0);
+
+    println!(
+        "{name:<12} {:>8} KiB {tokens:>9} tokens {elapsed:>12.3?}/run {throughput:>9.2} MiB/s",
+        code.len() / 1024,
+    );
+}
@@ -0,0 +1,99 @@
+/*
+ * C interface of the luau-lexer crate, built with the `ffi` feature.
+ *
+ * This header mirrors `src/ffi.rs`, keep them in sync. Build the static
+ * library with
+ *
+ *     cargo rustc --release --lib --features ffi --crate-type staticlib
+ *
+ * and link against it, along with `-lpthread -ldl -lm` on Linux.
+ *
+ * Tokens are lexed one at a time with `luau_lexer_next_token`. Their texts
+ * point into the lexer's copy of the code and aren't null-terminated, they
+ * stay valid until the lexer is freed with `luau_lexer_free`.
+ */
+
+#ifndef LUAU_LEXER_H
+#define LUAU_LEXER_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The kinds of tokens, for `LuauToken::kind`. */
+#define LUAU_TOKEN_ERROR 0
+#define LUAU_TOKEN_NUMBER 1
+#define LUAU_TOKEN_STRING 2
+#define LUAU_TOKEN_BOOLEAN 3
+#define LUAU_TOKEN_IDENTIFIER 4
+#define LUAU_TOKEN_COMMENT 5
+#define LUAU_TOKEN_KEYWORD 6
+#define LUAU_TOKEN_PARTIAL_KEYWORD 7
+#define LUAU_TOKEN_SYMBOL 8
+#define LUAU_TOKEN_OPERATOR 9
+#define LUAU_TOKEN_COMPOUND_OPERATOR 10
+#define LUAU_TOKEN_END_OF_FILE 11
+#define LUAU_TOKEN_SPACES 12
+
+/* `LuauToken::error_index` of tokens which aren't errors. */
+#define LUAU_NO_ERROR SIZE_MAX
+
+/* An opaque lexer handle. */
+typedef struct LuauLexer LuauLexer;
+
+/* A token, without its trivia. Lines and columns are zero-based, columns are
+ * in characters, and all ends are exclusive. */
+typedef struct LuauToken {
+    uint32_t kind;
+    uint32_t start_line;
+    uint32_t start_column;
+    uint32_t end_line;
+    uint32_t end_column;
+    size_t start_offset;
+    size_t end_offset;
+    const char *text;
+    size_t text_length;
+    /* The index of the error for `luau_lexer_error`, or `LUAU_NO_ERROR`. */
+    size_t error_index;
+} LuauToken;
+
+/* An error met while lexing. */
+typedef struct LuauError {
+    const char *message;
+    size_t message_length;
+    uint32_t start_line;
+    uint32_t start_column;
+    uint32_t end_line;
+    uint32_t end_column;
+    bool is_warning;
+} LuauError;
+
+/* Create a lexer over a copy of the passed UTF-8 code. Returns null if the
+ * code isn't valid UTF-8. `code` can only be null if `length` is 0. */
+LuauLexer *luau_lexer_new(const char *code, size_t length);
+
+/* Free a lexer, invalidating all texts it returned. Passing null does
+ * nothing. */
+void luau_lexer_free(LuauLexer *lexer);
+
+/* Lex the next token into `token`. Returns false, without writing anything,
+ * once the end of file token was returned or if either pointer is null. */
+bool luau_lexer_next_token(LuauLexer *lexer, LuauToken *token);
+
+/* Get the number of errors returned so far. */
+size_t luau_lexer_error_count(const LuauLexer *lexer);
+
+/* Get the error at the passed index into `error`. Returns false, without
+ * writing anything, if the index is out of bounds or if either pointer is
+ * null. */
+bool luau_lexer_error(const LuauLexer *lexer, size_t index, LuauError *error);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LUAU_LEXER_H */
@@ -0,0 +1,406 @@
+//! `luau-lex`, lexes files and prints their tokens.
+//!
+//! Run `luau-lex --help` for the available options.
+
+use luau_lexer::prelude::{
+    Dialect, LexerConfig, RecordKind, RecordSeverity, SourceRange, TokenDump, TokenRecord,
+    TriviaRecord,
+};
+use serde::Serialize;
+use std::{
+    env, fs,
+    io::{self, BufWriter, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// The help message.
+const HELP: &str = "\
+Lex luau files and print their tokens.
+
+Usage: luau-lex [OPTIONS] [PATHS]...
+
+Paths can be files, directories, which are searched for `.luau` and `.lua`
+files, or glob patterns. Standard input is read when no path, or `-`, is passed.
+
+Options:
+  -f, --format <FORMAT>    The output format: `table`, `json`, or `jsonl` [default: table]
+  -d, --dialect <DIALECT>  The dialect: `luau`, `lua51`, `lua52`, `lua53`, or `lua54` [default: luau]
+  -t, --trivia             Include the trivia around tokens
+  -e, --errors             Only print errors
+  -h, --help               Print this message
+
+The exit code is 1 if any file has lexical errors, and 2 if a file can't be read.";
+
+/// How tokens are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Format {
+    /// An aligned table, one token per line.
+    #[default]
+    Table,
+
+    /// A single JSON array with an object per file.
+    Json,
+
+    /// A JSON object per token, one per line.
+    JsonLines,
+}
+
+/// The parsed command line arguments.
+#[derive(Clone, Debug, Default)]
+struct Options {
+    /// How tokens are printed.
+    format: Format,
+
+    /// The dialect to lex.
+    dialect: Dialect,
+
+    /// Whether or not to include trivia.
+    trivia: bool,
+
+    /// Whether or not to only print errors.
+    errors_only: bool,
+
+    /// The paths and patterns to lex, empty for standard input.
+    paths: Vec<String>,
+}
+
+impl Options {
+    /// Parse the passed arguments, returning `None` if the help message should
+    /// be printed instead.
+    fn parse(mut arguments: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut options = Self::default();
+
+        while let Some(argument) = arguments.next() {
+            let (flag, inline_value) = match argument.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (argument.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| arguments.next())
+                    .ok_or_else(|| format!("`{flag}` expects a value."))
+            };
+
+            match flag {
+                "-h" | "--help" => return Ok(None),
+                "-t" | "--trivia" => options.trivia = true,
+                "-e" | "--errors" => options.errors_only = true,
+                "-f" | "--format" => {
+                    options.format = match value()?.as_str() {
+                        "table" => Format::Table,
+                        "json" => Format::Json,
+                        "jsonl" => Format::JsonLines,
+                        format => return Err(format!("Unknown format `{format}`.")),
+                    };
+                }
+                "-d" | "--dialect" => {
+                    options.dialect = match value()?.to_lowercase().as_str() {
+                        "luau" => Dialect::Luau,
+                        "lua51" => Dialect::Lua51,
+                        "lua52" => Dialect::Lua52,
+                        "lua53" => Dialect::Lua53,
+                        "lua54" => Dialect::Lua54,
+                        dialect => return Err(format!("Unknown dialect `{dialect}`.")),
+                    };
+                }
+                _ if flag.starts_with('-') && flag != "-" => {
+                    return Err(format!("Unknown option `{flag}`."));
+                }
+                _ => options.paths.push(argument),
+            }
+        }
+
+        Ok(Some(options))
+    }
+}
+
+/// A lexed file.
+#[derive(Clone, Debug, Serialize)]
+struct File {
+    /// The path of the file, `-` for standard input.
+    path: String,
+
+    /// The number of errors in the file, excluding warnings.
+    errors: usize,
+
+    /// The printed tokens.
+    #[serde(flatten)]
+    dump: TokenDump,
+}
+
+/// A single token of a file, for [`Format::JsonLines`].
+#[derive(Clone, Debug, Serialize)]
+struct Line<'a> {
+    /// The version of the serialization model.
+    schema_version: u32,
+
+    /// The path of the file.
+    path: &'a str,
+
+    /// The token.
+    token: &'a TokenRecord,
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{HELP}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{HELP}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let (paths, mut failed) = collect_paths(&options.paths);
+    let mut files = Vec::new();
+
+    for path in paths {
+        match read(&path) {
+            Ok(code) => files.push(lex(path, &code, &options)),
+            Err(error) => {
+                eprintln!("error: {path}: {error}");
+                failed = true;
+            }
+        }
+    }
+
+    let result = match options.format {
+        Format::Table => print_table(&files, &options),
+        Format::Json => print_json(&files),
+        Format::JsonLines => print_json_lines(&files),
+    };
+    match result {
+        Err(error) if error.kind() != ErrorKind::BrokenPipe => {
+            eprintln!("error: {error}");
+            failed = true;
+        }
+        _ => (),
+    }
+
+    if failed {
+        ExitCode::from(2)
+    } else if files.iter().any(|file| file.errors > 0) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Resolve the passed paths, directories, and patterns into the files to lex,
+/// reporting the ones matching nothing. The returned boolean is whether or not
+/// any of them failed.
+fn collect_paths(arguments: &[String]) -> (Vec<String>, bool) {
+    if arguments.is_empty() {
+        return (vec!["-".to_string()], false);
+    }
+
+    let mut paths = Vec::new();
+    let mut failed = false;
+
+    for argument in arguments {
+        let path = Path::new(argument);
+
+        if argument == "-" || path.is_file() {
+            paths.push(argument.clone());
+        } else if path.is_dir() {
+            collect_directory(path, &mut paths, &mut failed);
+        } else {
+            let matches = glob::glob(argument)
+                .map(|matches| matches.flatten().collect::<Vec<_>>())
+                .unwrap_or_default();
+            if matches.is_empty() {
+                eprintln!("error: `{argument}` doesn't match any file.");
+                failed = true;
+            }
+
+            for path in matches {
+                if path.is_dir() {
+                    collect_directory(&path, &mut paths, &mut failed);
+                } else {
+                    paths.push(path.display().to_string());
+                }
+            }
+        }
+    }
+
+    (paths, failed)
+}
+
+/// Add all luau and lua files in the passed directory, recursively, in order.
+fn collect_directory(directory: &Path, paths: &mut Vec<String>, failed: &mut bool) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        eprintln!("error: can't read `{}`.", directory.display());
+        *failed = true;
+        return;
+    };
+
+    let mut entries = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .collect::<Vec<PathBuf>>();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_directory(&path, paths, failed);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "luau" || extension == "lua")
+        {
+            paths.push(path.display().to_string());
+        }
+    }
+}
+
+/// Read the file at the passed path, or standard input for `-`.
+fn read(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut code = String::new();
+        io::stdin().read_to_string(&mut code)?;
+
+        Ok(code)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+/// Lex the passed code, keeping the tokens which should be printed.
+fn lex(path: String, code: &str, options: &Options) -> File {
+    let mut dump =
+        TokenDump::from_lexer(&mut LexerConfig::new().with_dialect(options.dialect).build(code));
+
+    let errors = dump
+        .tokens
+        .iter()
+        .filter_map(|token| token.error.as_ref())
+        .filter(|error| error.severity == RecordSeverity::Error)
+        .count();
+
+    if options.errors_only {
+        dump.tokens.retain(|token| token.kind == RecordKind::Error);
+    }
+    if !options.trivia {
+        for token in &mut dump.tokens {
+            token.leading_trivia.clear();
+            token.trailing_trivia.clear();
+        }
+    }
+
+    File { path, errors, dump }
+}
+
+/// Print the files as aligned tables.
+fn print_table(files: &[File], options: &Options) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+
+    for file in files {
+        if files.len() > 1 || file.path != "-" {
+            writeln!(out, "{}", file.path)?;
+        }
+
+        let mut rows = Vec::new();
+        for (i, token) in file.dump.tokens.iter().enumerate() {
+            // Trivia between two tokens is both trailing and leading, so only the
+            // first token's leading trivia is shown.
+            if i == 0 {
+                rows.extend(token.leading_trivia.iter().map(trivia_row));
+            }
+
+            rows.push(token_row(token));
+            rows.extend(token.trailing_trivia.iter().map(trivia_row));
+        }
+
+        let range_width = rows.iter().map(|row| row.0.len()).max().unwrap_or(0);
+        let kind_width = rows.iter().map(|row| row.1.len()).max().unwrap_or(0);
+
+        for (range, kind, text) in rows {
+            writeln!(out, "  {range:<range_width$}  {kind:<kind_width$}  {text}")?;
+        }
+
+        if options.errors_only && file.dump.tokens.is_empty() {
+            writeln!(out, "  No errors.")?;
+        }
+    }
+
+    out.flush()
+}
+
+/// Get the range, kind, and text columns of the passed token.
+fn token_row(token: &TokenRecord) -> (String, String, String) {
+    // Errors are placed after the token they belong to, but point at it.
+    let (range, text) = match &token.error {
+        Some(error) => (error.range, error.message.clone()),
+        None => (token.range, escape(&token.text)),
+    };
+
+    (format_range(range), format!("{:?}", token.kind), text)
+}
+
+/// Get the range, kind, and text columns of the passed trivia.
+fn trivia_row(trivia: &TriviaRecord) -> (String, String, String) {
+    (
+        String::new(),
+        format!("({:?})", trivia.kind),
+        escape(&trivia.text),
+    )
+}
+
+/// Format the passed range with one-based lines and columns.
+fn format_range(range: SourceRange) -> String {
+    format!(
+        "{}:{}-{}:{}",
+        range.start.line + 1,
+        range.start.column + 1,
+        range.end.line + 1,
+        range.end.column + 1
+    )
+}
+
+/// Escape control characters, like new lines, to keep the text on one line.
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|character| {
+            if character.is_control() {
+                character.escape_default().to_string()
+            } else {
+                character.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Print the files as a JSON array.
+fn print_json(files: &[File]) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+
+    serde_json::to_writer_pretty(&mut out, files)?;
+    writeln!(out)?;
+
+    out.flush()
+}
+
+/// Print every token of the files as a JSON object on its own line.
+fn print_json_lines(files: &[File]) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+
+    for file in files {
+        for token in &file.dump.tokens {
+            serde_json::to_writer(
+                &mut out,
+                &Line {
+                    schema_version: file.dump.schema_version,
+                    path: &file.path,
+                    token,
+                },
+            )?;
+            writeln!(out)?;
+        }
+    }
+
+    out.flush()
+}
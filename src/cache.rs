@@ -0,0 +1,755 @@
+//! The [`TokenCache`] struct, a compact binary encoding of lexed tokens, meant
+//! to be persisted between sessions so unchanged files aren't lexed again.
+//!
+//! # Format
+//!
+//! All numbers are LEB128 varints unless stated otherwise, and strings are
+//! their length followed by their UTF-8 bytes.
+//!
+//! * [`MAGIC`], then the format version, [`CACHE_VERSION`], and the version of
+//!   this crate as a string. Caches written by other versions are rejected as
+//!   tokens may be lexed differently.
+//! * An FNV-1a hash, as 8 little-endian bytes, of everything after it.
+//! * The FNV-1a hashes of the code and of the [`LexerConfig`], 8 little-endian
+//!   bytes each.
+//! * A table of all distinct strings, referenced by their index afterwards.
+//! * The tokens, with their lines encoded relative to the previous token, and
+//!   leading trivia only stored when it's not the previous trailing trivia.
+//!   Lists of trivia store where their first item starts, the others start
+//!   where the previous one ends.
+
+use lsp_types::Position;
+use smol_str::SmolStr;
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    io::{self, Read, Write},
+};
+
+use crate::prelude::{
+    Comment, CompoundOperator, Error, Interner, Keyword, Lexer, LexerConfig, LexerInput, Literal,
+    LuauNumber, LuauString, Operator, PartialKeyword, Severity, Symbol, Token, TokenType, Trivia,
+    TriviaType,
+};
+
+/// The bytes every cache starts with.
+pub const MAGIC: [u8; 4] = *b"LXTC";
+
+/// The version of the format, bumped whenever it changes.
+pub const CACHE_VERSION: u32 = 3;
+
+/// The version of this crate.
+const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The tags identifying each token type and trivia in the cache.
+mod tag {
+    /// [`TokenType::Error`](crate::token::TokenType::Error)
+    pub const ERROR: u8 = 0;
+    /// [`LuauNumber::Plain`](crate::token::LuauNumber::Plain)
+    pub const PLAIN_NUMBER: u8 = 1;
+    /// [`LuauNumber::Binary`](crate::token::LuauNumber::Binary)
+    pub const BINARY_NUMBER: u8 = 2;
+    /// [`LuauNumber::Hex`](crate::token::LuauNumber::Hex)
+    pub const HEX_NUMBER: u8 = 3;
+    /// [`LuauString::SingleQuotes`](crate::token::LuauString::SingleQuotes)
+    pub const SINGLE_QUOTES: u8 = 4;
+    /// [`LuauString::DoubleQuotes`](crate::token::LuauString::DoubleQuotes)
+    pub const DOUBLE_QUOTES: u8 = 5;
+    /// [`LuauString::Backticks`](crate::token::LuauString::Backticks)
+    pub const BACKTICKS: u8 = 6;
+    /// [`LuauString::MultiLine`](crate::token::LuauString::MultiLine)
+    pub const MULTI_LINE_STRING: u8 = 7;
+    /// `true`
+    pub const TRUE: u8 = 8;
+    /// `false`
+    pub const FALSE: u8 = 9;
+    /// [`TokenType::Identifier`](crate::token::TokenType::Identifier)
+    pub const IDENTIFIER: u8 = 10;
+    /// [`Comment::SingleLine`](crate::token::Comment::SingleLine)
+    pub const SINGLE_LINE_COMMENT: u8 = 11;
+    /// [`Comment::MultiLine`](crate::token::Comment::MultiLine)
+    pub const MULTI_LINE_COMMENT: u8 = 12;
+    /// [`TokenType::Keyword`](crate::token::TokenType::Keyword)
+    pub const KEYWORD: u8 = 13;
+    /// [`TokenType::PartialKeyword`](crate::token::TokenType::PartialKeyword)
+    pub const PARTIAL_KEYWORD: u8 = 14;
+    /// [`TokenType::Symbol`](crate::token::TokenType::Symbol)
+    pub const SYMBOL: u8 = 15;
+    /// [`TokenType::Operator`](crate::token::TokenType::Operator)
+    pub const OPERATOR: u8 = 16;
+    /// [`TokenType::CompoundOperator`](crate::token::TokenType::CompoundOperator)
+    pub const COMPOUND_OPERATOR: u8 = 17;
+    /// [`TokenType::EndOfFile`](crate::token::TokenType::EndOfFile)
+    pub const END_OF_FILE: u8 = 18;
+    /// [`TriviaType::Spaces`](crate::token::TriviaType::Spaces) and
+    /// [`TokenType::Spaces`](crate::token::TokenType::Spaces)
+    pub const SPACES: u8 = 19;
+}
+
+/// Why a [`TokenCache`] couldn't be read.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub enum CacheError {
+    /// Reading failed.
+    Io(io::Error),
+
+    /// The data doesn't start with [`MAGIC`], it isn't a cache.
+    NotACache,
+
+    /// The cache was written with another version of the format.
+    UnsupportedVersion(u32),
+
+    /// The cache was written by another version of this crate.
+    CrateVersion(String),
+
+    /// The code or the configuration changed since the cache was written.
+    Stale,
+
+    /// The cache is truncated or its content was modified.
+    Corrupted,
+}
+
+impl Display for CacheError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Couldn't read the cache: {error}"),
+            Self::NotACache => f.write_str("The data isn't a token cache."),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported cache format version {version}.")
+            }
+            Self::CrateVersion(version) => write!(
+                f,
+                "The cache was written by version {version}, not {CRATE_VERSION}."
+            ),
+            Self::Stale => f.write_str("The code or configuration changed since caching."),
+            Self::Corrupted => f.write_str("The cache is corrupted."),
+        }
+    }
+}
+
+impl error::Error for CacheError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CacheError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// The 64-bit FNV-1a hash. It's used over [`DefaultHasher`](std::hash::DefaultHasher)
+/// as its output must not change between runs.
+#[derive(Clone, Copy, Debug)]
+struct Fnv(u64);
+
+impl Default for Fnv {
+    #[inline]
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Hash the passed bytes with [`Fnv`].
+#[inline]
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = Fnv::default();
+    hasher.write(bytes);
+
+    hasher.finish()
+}
+
+/// Hash the passed configuration with [`Fnv`].
+#[inline]
+fn hash_config(config: &LexerConfig) -> u64 {
+    let mut hasher = Fnv::default();
+    config.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+/// The tokens of a file along with what's needed to know whether or not they're
+/// still valid for it.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct TokenCache {
+    /// The hash of the lexed code.
+    code_hash: u64,
+
+    /// The hash of the [`LexerConfig`] used.
+    config_hash: u64,
+
+    /// The tokens, the last one being [`TokenType::EndOfFile`].
+    tokens: Vec<Token>,
+}
+
+impl TokenCache {
+    /// Lex the passed code and cache its tokens.
+    #[inline]
+    pub fn new(code: &str) -> Self {
+        Self::from_lexer(&mut Lexer::new(code))
+    }
+
+    /// Cache all remaining tokens in the passed lexer. The lexer is expected to
+    /// be at the start of its input.
+    pub fn from_lexer<I: LexerInput>(lexer: &mut Lexer<I>) -> Self {
+        let tokens = lexer.collect_tokens();
+        let input = lexer.input();
+
+        Self {
+            code_hash: hash_bytes(input.slice(0..input.len()).as_bytes()),
+            config_hash: hash_config(lexer.config()),
+            tokens,
+        }
+    }
+
+    /// Get the cached tokens.
+    #[inline]
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Take the cached tokens out.
+    #[inline]
+    pub fn into_tokens(self) -> Vec<Token> {
+        self.tokens
+    }
+
+    /// Whether or not the tokens are the ones of the passed code lexed with
+    /// the passed configuration.
+    #[inline]
+    pub fn is_fresh(&self, code: &str, config: &LexerConfig) -> bool {
+        self.code_hash == hash_bytes(code.as_bytes()) && self.config_hash == hash_config(config)
+    }
+
+    /// Encode the cache and write it to the passed writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error met while writing.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut interner = Interner::new();
+        let mut tokens = Encoder::default();
+        let mut previous: Option<&Token> = None;
+
+        tokens.varint(self.tokens.len() as u64);
+        for token in &self.tokens {
+            tokens.token(token, previous, &mut interner);
+            previous = Some(token);
+        }
+
+        let mut body = Encoder::default();
+        body.u64(self.code_hash);
+        body.u64(self.config_hash);
+        body.varint(interner.len() as u64);
+        for string in Vec::<SmolStr>::from(interner) {
+            body.string(&string);
+        }
+        body.bytes.extend(tokens.bytes);
+
+        let mut header = Encoder::default();
+        header.bytes.extend(MAGIC);
+        header.varint(u64::from(CACHE_VERSION));
+        header.string(CRATE_VERSION);
+        header.u64(hash_bytes(&body.bytes));
+
+        writer.write_all(&header.bytes)?;
+        writer.write_all(&body.bytes)
+    }
+
+    /// Read a cache written by [`TokenCache::write_to`], checking it's still
+    /// valid for the passed code and configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails, if the cache was written by another
+    /// version, if the code or configuration changed, or if it's corrupted.
+    pub fn read_from(
+        mut reader: impl Read,
+        code: &str,
+        config: &LexerConfig,
+    ) -> Result<Self, CacheError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let cache = Self::decode(&bytes)?;
+        if cache.is_fresh(code, config) {
+            Ok(cache)
+        } else {
+            Err(CacheError::Stale)
+        }
+    }
+
+    /// Decode the passed bytes, without checking whether or not they're fresh.
+    fn decode(bytes: &[u8]) -> Result<Self, CacheError> {
+        if !bytes.starts_with(&MAGIC) {
+            return Err(CacheError::NotACache);
+        }
+
+        let mut decoder = Decoder::new(&bytes[MAGIC.len()..]);
+
+        let version = decoder.varint()?;
+        if version != u64::from(CACHE_VERSION) {
+            return Err(CacheError::UnsupportedVersion(
+                u32::try_from(version).unwrap_or(u32::MAX),
+            ));
+        }
+
+        let crate_version = decoder.string()?;
+        if crate_version != CRATE_VERSION {
+            return Err(CacheError::CrateVersion(crate_version.to_string()));
+        }
+
+        let checksum = decoder.u64()?;
+        if hash_bytes(decoder.bytes) != checksum {
+            return Err(CacheError::Corrupted);
+        }
+
+        let code_hash = decoder.u64()?;
+        let config_hash = decoder.u64()?;
+
+        let strings_count = decoder.length()?;
+        // Counts aren't trusted for capacities, every item is at least a byte.
+        decoder.strings = Vec::with_capacity(strings_count.min(decoder.bytes.len()));
+        for _ in 0..strings_count {
+            let string = SmolStr::new(decoder.string()?);
+            decoder.strings.push(string);
+        }
+
+        let tokens_count = decoder.length()?;
+        let mut tokens = Vec::with_capacity(tokens_count.min(decoder.bytes.len()));
+        for _ in 0..tokens_count {
+            let token = decoder.token(tokens.last())?;
+            tokens.push(token);
+        }
+
+        if !decoder.bytes.is_empty() {
+            return Err(CacheError::Corrupted);
+        }
+
+        Ok(Self {
+            code_hash,
+            config_hash,
+            tokens,
+        })
+    }
+}
+
+/// Encodes the parts of a [`TokenCache`].
+#[derive(Clone, Debug, Default)]
+struct Encoder {
+    /// The encoded bytes.
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    /// Write a LEB128 varint.
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+
+        self.bytes.push(value as u8);
+    }
+
+    /// Write a signed varint, zigzag-encoded so small negative numbers stay
+    /// small.
+    #[inline]
+    fn signed(&mut self, value: i64) {
+        self.varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    /// Write 8 little-endian bytes.
+    #[inline]
+    fn u64(&mut self, value: u64) {
+        self.bytes.extend(value.to_le_bytes());
+    }
+
+    /// Write a string with its length.
+    #[inline]
+    fn string(&mut self, string: &str) {
+        self.varint(string.len() as u64);
+        self.bytes.extend(string.as_bytes());
+    }
+
+    /// Write the index of the passed string in the string table.
+    #[inline]
+    fn interned(&mut self, string: &str, interner: &mut Interner) {
+        self.varint(interner.intern(string).index() as u64);
+    }
+
+    /// Write a position with its line relative to the passed line.
+    #[inline]
+    fn position(&mut self, position: Position, line: u32) {
+        self.signed(i64::from(position.line) - i64::from(line));
+        self.varint(u64::from(position.character));
+    }
+
+    /// Write the end of a range relative to its start. The column is also
+    /// relative when both are on the same line, which is most of the time.
+    #[inline]
+    fn end(&mut self, end: Position, start: Position) {
+        self.signed(i64::from(end.line) - i64::from(start.line));
+
+        if end.line == start.line {
+            self.signed(i64::from(end.character) - i64::from(start.character));
+        } else {
+            self.varint(u64::from(end.character));
+        }
+    }
+
+    /// Write a list of trivia, without its length, with the lines relative to
+    /// the passed line. Trivia are contiguous, so only where the first one
+    /// starts is written.
+    fn trivia(&mut self, trivia: &[Trivia], line: u32, interner: &mut Interner) {
+        if let Some(first) = trivia.first() {
+            self.position(first.start, line);
+            self.varint(first.start_offset as u64);
+        }
+
+        for trivia in trivia {
+            let (tag, text) = match &trivia.trivia_type {
+                TriviaType::Spaces(spaces) => (tag::SPACES, spaces),
+                TriviaType::Comment(Comment::SingleLine(text)) => (tag::SINGLE_LINE_COMMENT, text),
+                TriviaType::Comment(Comment::MultiLine(text)) => (tag::MULTI_LINE_COMMENT, text),
+            };
+
+            self.bytes.push(tag);
+            self.interned(text, interner);
+            self.end(trivia.end, trivia.start);
+        }
+    }
+
+    /// Write a token.
+    fn token(&mut self, token: &Token, previous: Option<&Token>, interner: &mut Interner) {
+        let (tag, text) = match &token.token_type {
+            TokenType::Error(_) => (tag::ERROR, None),
+            TokenType::Literal(Literal::Number(number)) => match number {
+                LuauNumber::Plain(text) => (tag::PLAIN_NUMBER, Some(text)),
+                LuauNumber::Binary(text) => (tag::BINARY_NUMBER, Some(text)),
+                LuauNumber::Hex(text) => (tag::HEX_NUMBER, Some(text)),
+            },
+            TokenType::Literal(Literal::String(string)) => match string {
+                LuauString::SingleQuotes(text) => (tag::SINGLE_QUOTES, Some(text)),
+                LuauString::DoubleQuotes(text) => (tag::DOUBLE_QUOTES, Some(text)),
+                LuauString::Backticks(text) => (tag::BACKTICKS, Some(text)),
+                LuauString::MultiLine(text) => (tag::MULTI_LINE_STRING, Some(text)),
+            },
+            TokenType::Literal(Literal::Boolean(true)) => (tag::TRUE, None),
+            TokenType::Literal(Literal::Boolean(false)) => (tag::FALSE, None),
+            TokenType::Identifier(text) => (tag::IDENTIFIER, Some(text)),
+            TokenType::Comment(Comment::SingleLine(text)) => (tag::SINGLE_LINE_COMMENT, Some(text)),
+            TokenType::Comment(Comment::MultiLine(text)) => (tag::MULTI_LINE_COMMENT, Some(text)),
+            TokenType::Spaces(text) => (tag::SPACES, Some(text)),
+            TokenType::Keyword(_) => (tag::KEYWORD, None),
+            TokenType::PartialKeyword(_) => (tag::PARTIAL_KEYWORD, None),
+            TokenType::Symbol(_) => (tag::SYMBOL, None),
+            TokenType::Operator(_) => (tag::OPERATOR, None),
+            TokenType::CompoundOperator(_) => (tag::COMPOUND_OPERATOR, None),
+            TokenType::EndOfFile => (tag::END_OF_FILE, None),
+        };
+
+        self.bytes.push(tag);
+        self.position(
+            token.start,
+            previous.map_or(0, |previous| previous.end.line),
+        );
+        self.end(token.end, token.start);
+
+        if let Some(text) = text {
+            self.interned(text, interner);
+        }
+        match &token.token_type {
+            TokenType::Error(error) => self.error(error, token.start.line, interner),
+            TokenType::Keyword(keyword) => self.bytes.push(*keyword as u8),
+            TokenType::PartialKeyword(keyword) => self.bytes.push(*keyword as u8),
+            TokenType::Symbol(symbol) => self.bytes.push(*symbol as u8),
+            TokenType::Operator(operator) => self.bytes.push(*operator as u8),
+            TokenType::CompoundOperator(operator) => self.bytes.push(*operator as u8),
+            _ => (),
+        }
+
+        // Trivia between two tokens is both trailing and leading, so it's only
+        // written once. `0` marks it as shared, anything else is its length + 1.
+        if previous.is_some_and(|previous| previous.trailing_trivia == token.leading_trivia) {
+            self.varint(0);
+        } else {
+            self.varint(token.leading_trivia.len() as u64 + 1);
+            self.trivia(&token.leading_trivia, token.start.line, interner);
+        }
+
+        self.varint(token.trailing_trivia.len() as u64);
+        self.trivia(&token.trailing_trivia, token.end.line, interner);
+    }
+
+    /// Write an error, with its lines relative to the passed line.
+    fn error(&mut self, error: &Error, line: u32, interner: &mut Interner) {
+        self.interned(error.message(), interner);
+        self.position(error.start(), line);
+
+        match error.end() {
+            Some(end) => {
+                self.bytes.push(1);
+                self.end(end, error.start());
+            }
+            None => self.bytes.push(0),
+        }
+
+        self.bytes.push(match error.severity() {
+            Severity::Error => 0,
+            Severity::Warning => 1,
+        });
+    }
+}
+
+/// Decodes the parts of a [`TokenCache`]. Any invalid data is reported as
+/// [`CacheError::Corrupted`].
+#[derive(Clone, Debug)]
+struct Decoder<'a> {
+    /// The bytes left to decode.
+    bytes: &'a [u8],
+
+    /// The string table.
+    strings: Vec<SmolStr>,
+}
+
+impl<'a> Decoder<'a> {
+    /// Create a new [`Decoder`] over the passed bytes.
+    #[inline]
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            strings: Vec::new(),
+        }
+    }
+
+    /// Read the passed number of bytes.
+    const fn take(&mut self, count: usize) -> Result<&'a [u8], CacheError> {
+        let Some((bytes, rest)) = self.bytes.split_at_checked(count) else {
+            return Err(CacheError::Corrupted);
+        };
+
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    /// Read a single byte.
+    #[inline]
+    fn byte(&mut self) -> Result<u8, CacheError> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Read a LEB128 varint.
+    fn varint(&mut self) -> Result<u64, CacheError> {
+        let mut value = 0;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7F) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(CacheError::Corrupted)
+    }
+
+    /// Read a varint which is a length or an index.
+    #[inline]
+    fn length(&mut self) -> Result<usize, CacheError> {
+        usize::try_from(self.varint()?).map_err(|_| CacheError::Corrupted)
+    }
+
+    /// Read a zigzag-encoded signed varint.
+    #[inline]
+    fn signed(&mut self) -> Result<i64, CacheError> {
+        let value = self.varint()?;
+
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    /// Read 8 little-endian bytes.
+    fn u64(&mut self) -> Result<u64, CacheError> {
+        let bytes = self.take(8)?;
+        let mut array = [0; 8];
+        array.copy_from_slice(bytes);
+
+        Ok(u64::from_le_bytes(array))
+    }
+
+    /// Read a string with its length.
+    fn string(&mut self) -> Result<&'a str, CacheError> {
+        let length = self.length()?;
+
+        str::from_utf8(self.take(length)?).map_err(|_| CacheError::Corrupted)
+    }
+
+    /// Read the index of a string and get it from the string table.
+    fn interned(&mut self) -> Result<SmolStr, CacheError> {
+        let index = self.length()?;
+
+        self.strings
+            .get(index)
+            .cloned()
+            .ok_or(CacheError::Corrupted)
+    }
+
+    /// Read a position with its line relative to the passed line.
+    fn position(&mut self, line: u32) -> Result<Position, CacheError> {
+        let line = u32::try_from(i64::from(line) + self.signed()?);
+        let character = u32::try_from(self.varint()?);
+
+        match (line, character) {
+            (Ok(line), Ok(character)) => Ok(Position::new(line, character)),
+            _ => Err(CacheError::Corrupted),
+        }
+    }
+
+    /// Read the end of a range written relative to its start.
+    fn end(&mut self, start: Position) -> Result<Position, CacheError> {
+        let line = u32::try_from(i64::from(start.line) + self.signed()?);
+        let character = match line {
+            Ok(line) if line == start.line => {
+                u32::try_from(i64::from(start.character) + self.signed()?)
+            }
+            _ => u32::try_from(self.varint()?),
+        };
+
+        match (line, character) {
+            (Ok(line), Ok(character)) => Ok(Position::new(line, character)),
+            _ => Err(CacheError::Corrupted),
+        }
+    }
+
+    /// Read the index of an item and get it from the passed list.
+    fn item<T: Copy>(&mut self, items: &[T]) -> Result<T, CacheError> {
+        let index = usize::from(self.byte()?);
+
+        items.get(index).copied().ok_or(CacheError::Corrupted)
+    }
+
+    /// Read the passed number of trivia, with their lines relative to the
+    /// passed line.
+    fn trivia(&mut self, count: usize, line: u32) -> Result<Vec<Trivia>, CacheError> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut start = self.position(line)?;
+        let mut start_offset = self.length()?;
+
+        // The count isn't trusted for the capacity, every trivia is at least
+        // 4 bytes.
+        let mut trivia = Vec::with_capacity(count.min(self.bytes.len() / 4));
+
+        for _ in 0..count {
+            let tag = self.byte()?;
+            let text = self.interned()?;
+            let end = self.end(start)?;
+            let end_offset = start_offset
+                .checked_add(text.len())
+                .ok_or(CacheError::Corrupted)?;
+
+            let trivia_type = match tag {
+                tag::SPACES => TriviaType::Spaces(text),
+                tag::SINGLE_LINE_COMMENT => TriviaType::Comment(Comment::SingleLine(text)),
+                tag::MULTI_LINE_COMMENT => TriviaType::Comment(Comment::MultiLine(text)),
+                _ => return Err(CacheError::Corrupted),
+            };
+            trivia.push(trivia_type.into_trivia(start, end, start_offset, end_offset));
+
+            start = end;
+            start_offset = end_offset;
+        }
+
+        Ok(trivia)
+    }
+
+    /// Read an error, with its lines relative to the passed line.
+    fn error(&mut self, line: u32) -> Result<Error, CacheError> {
+        let message = self.interned()?;
+        let start = self.position(line)?;
+        let end = match self.byte()? {
+            0 => None,
+            1 => Some(self.end(start)?),
+            _ => return Err(CacheError::Corrupted),
+        };
+        let severity = match self.byte()? {
+            0 => Severity::Error,
+            1 => Severity::Warning,
+            _ => return Err(CacheError::Corrupted),
+        };
+
+        Ok(Error::new(start, message, end).with_severity(severity))
+    }
+
+    /// Read a token.
+    fn token(&mut self, previous: Option<&Token>) -> Result<Token, CacheError> {
+        let tag = self.byte()?;
+        let start = self.position(previous.map_or(0, |previous| previous.end.line))?;
+        let end = self.end(start)?;
+
+        let token_type = match tag {
+            tag::ERROR => TokenType::Error(self.error(start.line)?),
+            tag::PLAIN_NUMBER => Literal::Number(LuauNumber::Plain(self.interned()?)).into(),
+            tag::BINARY_NUMBER => Literal::Number(LuauNumber::Binary(self.interned()?)).into(),
+            tag::HEX_NUMBER => Literal::Number(LuauNumber::Hex(self.interned()?)).into(),
+            tag::SINGLE_QUOTES => {
+                Literal::String(LuauString::SingleQuotes(self.interned()?)).into()
+            }
+            tag::DOUBLE_QUOTES => {
+                Literal::String(LuauString::DoubleQuotes(self.interned()?)).into()
+            }
+            tag::BACKTICKS => Literal::String(LuauString::Backticks(self.interned()?)).into(),
+            tag::MULTI_LINE_STRING => {
+                Literal::String(LuauString::MultiLine(self.interned()?)).into()
+            }
+            tag::TRUE => Literal::Boolean(true).into(),
+            tag::FALSE => Literal::Boolean(false).into(),
+            tag::IDENTIFIER => TokenType::Identifier(self.interned()?),
+            tag::SINGLE_LINE_COMMENT => TokenType::Comment(Comment::SingleLine(self.interned()?)),
+            tag::MULTI_LINE_COMMENT => TokenType::Comment(Comment::MultiLine(self.interned()?)),
+            tag::SPACES => TokenType::Spaces(self.interned()?),
+            tag::KEYWORD => self.item(&Keyword::ALL)?.into(),
+            tag::PARTIAL_KEYWORD => self.item(&PartialKeyword::ALL)?.into(),
+            tag::SYMBOL => self.item(&Symbol::ALL)?.into(),
+            tag::OPERATOR => self.item(&Operator::ALL)?.into(),
+            tag::COMPOUND_OPERATOR => self.item(&CompoundOperator::ALL)?.into(),
+            tag::END_OF_FILE => TokenType::EndOfFile,
+            _ => return Err(CacheError::Corrupted),
+        };
+
+        let leading_trivia = match (self.length()?, previous) {
+            (0, Some(previous)) => previous.trailing_trivia.clone(),
+            (0, None) => return Err(CacheError::Corrupted),
+            (count, _) => self.trivia(count - 1, start.line)?,
+        };
+        let trailing_count = self.length()?;
+        let trailing_trivia = self.trivia(trailing_count, end.line)?;
+
+        Ok(token_type.into_token(start, end, leading_trivia, trailing_trivia))
+    }
+}
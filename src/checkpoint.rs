@@ -0,0 +1,47 @@
+//! The [`Checkpoint`] struct.
+
+use lsp_types::Position;
+
+/// A point in the input a [`Lexer`](crate::lexer::Lexer) can go back to with
+/// [`Lexer::rewind`](crate::lexer::Lexer::rewind).
+///
+/// Unlike [`State`](crate::state::State), it doesn't store the trivia before
+/// the next token, only where it starts, which makes creating it as cheap as
+/// copying a few numbers. The trivia is lexed again when rewinding instead.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// The byte offset of the lexer.
+    pub(crate) position: usize,
+
+    /// The [`Position`] of the lexer.
+    pub(crate) lexer_position: Position,
+
+    /// The byte offset at which the trivia before the next token starts.
+    pub(crate) trivia_start: usize,
+
+    /// The [`Position`] at which the trivia before the next token starts.
+    pub(crate) trivia_start_position: Position,
+
+    /// The number of errors reported so far.
+    pub(crate) errors_count: usize,
+
+    /// The number of errors met during lexing, returned or not.
+    pub(crate) errors_len: usize,
+
+    /// The number of errors which were already returned.
+    pub(crate) errors_read: usize,
+}
+
+impl Checkpoint {
+    /// Get the byte offset of the lexer at this checkpoint.
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.position
+    }
+
+    /// Get the [`Position`] of the lexer at this checkpoint.
+    #[inline]
+    pub const fn lexer_position(&self) -> Position {
+        self.lexer_position
+    }
+}
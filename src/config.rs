@@ -0,0 +1,296 @@
+//! The [`LexerConfig`] struct.
+
+use crate::{
+    dialect::Dialect,
+    error::Severity,
+    input::LexerInput,
+    lexer::Lexer,
+    utils::{can_be_identifier, is_ascii_whitespace, is_identifier_start},
+    version::{LuauFeatures, LuauVersion},
+};
+
+/// Which characters are considered whitespace.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Whitespace {
+    /// Any unicode whitespace, as in [`char::is_whitespace`].
+    #[default]
+    Unicode,
+
+    /// Only the whitespace luau itself accepts: spaces, tabs, new lines,
+    /// carriage returns, vertical tabs, and form feeds.
+    Ascii,
+}
+
+impl Whitespace {
+    /// Whether or not the passed character is whitespace.
+    #[inline]
+    pub const fn is_whitespace(self, character: char) -> bool {
+        if character.is_ascii() {
+            return is_ascii_whitespace(character as u8);
+        }
+
+        match self {
+            Self::Unicode => character.is_whitespace(),
+            Self::Ascii => false,
+        }
+    }
+}
+
+/// Which characters identifiers can be made of.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Identifiers {
+    /// Only what luau itself accepts: ascii letters, digits, and `_`.
+    #[default]
+    Ascii,
+
+    /// Also any unicode letter or digit, as accepted by Lua builds using a
+    /// locale which considers them letters.
+    Unicode,
+}
+
+impl Identifiers {
+    /// Whether or not the passed character can start an identifier.
+    #[inline]
+    pub fn is_identifier_start(self, character: char) -> bool {
+        if character.is_ascii() {
+            return is_identifier_start(character);
+        }
+
+        match self {
+            Self::Ascii => false,
+            Self::Unicode => character.is_alphabetic(),
+        }
+    }
+
+    /// Whether or not the passed character can be in an identifier.
+    #[inline]
+    pub fn can_be_identifier(self, character: char) -> bool {
+        if character.is_ascii() {
+            return can_be_identifier(character);
+        }
+
+        match self {
+            Self::Ascii => false,
+            Self::Unicode => character.is_alphanumeric(),
+        }
+    }
+}
+
+/// The configuration of a [`Lexer`]. It's stored in the lexer itself and not
+/// in its [`State`](crate::state::State), so saved states don't carry it.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[allow(clippy::module_name_repetitions)] // `Config` is too general.
+pub struct LexerConfig {
+    /// The dialect being lexed.
+    pub(crate) dialect: Dialect,
+
+    /// The features supported by the targeted luau runtime.
+    pub(crate) luau_features: LuauFeatures,
+
+    /// The severity of errors reported for syntax outside of `luau_features`.
+    pub(crate) unsupported_syntax: Severity,
+
+    /// Which characters are whitespace.
+    pub(crate) whitespace: Whitespace,
+
+    /// Which characters identifiers can be made of.
+    pub(crate) identifiers: Identifiers,
+
+    /// How many columns a tab takes.
+    pub(crate) tab_width: u32,
+
+    /// The maximum number of errors to report before the lexer stops.
+    pub(crate) max_errors: Option<usize>,
+
+    /// Whether or not comments are lexed as tokens instead of trivia.
+    pub(crate) comments_as_tokens: bool,
+
+    /// Whether or not spaces are lexed as tokens instead of trivia.
+    pub(crate) spaces_as_tokens: bool,
+}
+
+impl LexerConfig {
+    /// Create a new [`LexerConfig`] with the default configuration.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            dialect: Dialect::Luau,
+            luau_features: LuauFeatures::ALL,
+            unsupported_syntax: Severity::Error,
+            whitespace: Whitespace::Unicode,
+            identifiers: Identifiers::Ascii,
+            tab_width: 1,
+            max_errors: None,
+            comments_as_tokens: false,
+            spaces_as_tokens: false,
+        }
+    }
+
+    /// Create a [`Lexer`] with this configuration and the passed input.
+    #[inline]
+    pub fn build(self, input: &str) -> Lexer {
+        Lexer::from_parts(input.to_string(), self)
+    }
+
+    /// Create a [`Lexer`] with this configuration over the passed [`LexerInput`],
+    /// without copying it.
+    #[inline]
+    pub fn build_from<I: LexerInput>(self, input: I) -> Lexer<I> {
+        Lexer::from_parts(input, self)
+    }
+
+    /// Set the [`Dialect`] being lexed. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Set the [`LuauFeatures`] supported by the targeted runtime, tokens using
+    /// any other feature are reported. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_luau_features(mut self, luau_features: LuauFeatures) -> Self {
+        self.luau_features = luau_features;
+        self
+    }
+
+    /// Set the [`LuauFeatures`] to the ones of the passed [`LuauVersion`], the
+    /// one of the targeted runtime. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_luau_version(self, version: LuauVersion) -> Self {
+        self.with_luau_features(version.features())
+    }
+
+    /// Set whether syntax outside of [`LexerConfig::luau_features`] is rejected
+    /// or only warned about. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_unsupported_syntax(mut self, severity: Severity) -> Self {
+        self.unsupported_syntax = severity;
+        self
+    }
+
+    /// Set which characters are whitespace. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_whitespace(mut self, whitespace: Whitespace) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// Set which characters identifiers can be made of. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_identifiers(mut self, identifiers: Identifiers) -> Self {
+        self.identifiers = identifiers;
+        self
+    }
+
+    /// Set how many columns a tab takes in token positions, at least `1`. Meant
+    /// to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_tab_width(mut self, tab_width: u32) -> Self {
+        self.tab_width = if tab_width == 0 { 1 } else { tab_width };
+        self
+    }
+
+    /// Set the maximum number of errors to report. Once more errors are met,
+    /// a single error saying so is reported and the lexer stops, returning
+    /// [`TokenType::EndOfFile`](crate::token::TokenType::EndOfFile) from then
+    /// on. Warnings aren't counted. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_errors(mut self, max_errors: Option<usize>) -> Self {
+        self.max_errors = max_errors;
+        self
+    }
+
+    /// Set whether comments are lexed as [`TokenType::Comment`](crate::token::TokenType::Comment)
+    /// tokens instead of being part of the trivia. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_comments_as_tokens(mut self, comments_as_tokens: bool) -> Self {
+        self.comments_as_tokens = comments_as_tokens;
+        self
+    }
+
+    /// Set whether spaces are lexed as [`TokenType::Spaces`](crate::token::TokenType::Spaces)
+    /// tokens instead of being part of the trivia. Along with
+    /// [`LexerConfig::with_comments_as_tokens`], this makes the lexer return a
+    /// flat stream of tokens without any trivia. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_spaces_as_tokens(mut self, spaces_as_tokens: bool) -> Self {
+        self.spaces_as_tokens = spaces_as_tokens;
+        self
+    }
+
+    /// Get the [`Dialect`] being lexed.
+    #[inline]
+    pub const fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// Get the [`LuauFeatures`] supported by the targeted runtime.
+    #[inline]
+    pub const fn luau_features(&self) -> LuauFeatures {
+        self.luau_features
+    }
+
+    /// Get whether syntax outside of [`LexerConfig::luau_features`] is rejected
+    /// or only warned about.
+    #[inline]
+    pub const fn unsupported_syntax(&self) -> Severity {
+        self.unsupported_syntax
+    }
+
+    /// Get which characters are whitespace.
+    #[inline]
+    pub const fn whitespace(&self) -> Whitespace {
+        self.whitespace
+    }
+
+    /// Get which characters identifiers can be made of.
+    #[inline]
+    pub const fn identifiers(&self) -> Identifiers {
+        self.identifiers
+    }
+
+    /// Get how many columns a tab takes.
+    #[inline]
+    pub const fn tab_width(&self) -> u32 {
+        self.tab_width
+    }
+
+    /// Get the maximum number of errors to report.
+    #[inline]
+    pub const fn max_errors(&self) -> Option<usize> {
+        self.max_errors
+    }
+
+    /// Get whether comments are lexed as tokens.
+    #[inline]
+    pub const fn comments_as_tokens(&self) -> bool {
+        self.comments_as_tokens
+    }
+
+    /// Get whether spaces are lexed as tokens.
+    #[inline]
+    pub const fn spaces_as_tokens(&self) -> bool {
+        self.spaces_as_tokens
+    }
+}
+
+impl Default for LexerConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,308 @@
+//! The [`CursorContext`] enum.
+
+use lsp_types::Position;
+
+use crate::{
+    prelude::{
+        Bias, Comment, Keyword, Literal, LuauString, Symbol, TokenAt, TokenLocation, TokenStore,
+        TokenType, TriviaType,
+    },
+    utils::advance_position,
+};
+
+/// What's at the cursor, as far as the lexer can tell. This is meant to decide
+/// whether or not to show completions and which ones, and only relies on tokens
+/// so it works on code that doesn't parse.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub enum CursorContext {
+    /// In a comment.
+    Comment,
+
+    /// In a string.
+    String,
+
+    /// In a string which is missing its closing quote.
+    UnterminatedString,
+
+    /// In an expression inside a backtick string, like `` `{a|}` ``.
+    Interpolation,
+
+    /// In a number.
+    Number,
+
+    /// After a `.`, like `a.|`.
+    MemberAccess,
+
+    /// After a `:` in a method call or definition, like `a:|`.
+    MethodAccess,
+
+    /// In a type, after a `:` in an annotation, `::`, or `->`.
+    TypeAnnotation,
+
+    /// Naming a new local variable, after `local` or `for`.
+    LocalName,
+
+    /// Naming a new function, after `function`.
+    FunctionName,
+
+    /// Anywhere else.
+    Code,
+}
+
+impl CursorContext {
+    /// Get the context at the passed position in the passed code.
+    #[inline]
+    pub fn at(code: &str, position: Position) -> Self {
+        Self::from_store(&TokenStore::new(code), position)
+    }
+
+    /// Get the context at the passed position in an already lexed file.
+    pub fn from_store(store: &TokenStore, position: Position) -> Self {
+        // Nothing can be before the cursor.
+        if position == Position::default() {
+            return Self::Code;
+        }
+
+        let Some(at) = store.at_position(position, Bias::Left) else {
+            return Self::Code;
+        };
+
+        if let Some(context) = Self::literal_context(store, &at, position) {
+            return context;
+        }
+
+        // When typing a word, what matters is what's before it.
+        let anchor = match at.location {
+            TokenLocation::Token
+                if matches!(
+                    at.token.token_type,
+                    TokenType::Identifier(_)
+                        | TokenType::Keyword(_)
+                        | TokenType::PartialKeyword(_)
+                        | TokenType::Literal(Literal::Boolean(_))
+                ) =>
+            {
+                store.previous(at.index)
+            }
+            TokenLocation::LeadingTrivia(_) => None,
+            TokenLocation::Token | TokenLocation::TrailingTrivia(_) => Some(at.index),
+        };
+
+        let Some(anchor) = anchor else {
+            return Self::Code;
+        };
+
+        match store.tokens()[anchor].token_type {
+            TokenType::Symbol(Symbol::Dot) => Self::MemberAccess,
+            TokenType::Symbol(Symbol::Colon) if is_type_colon(store, anchor) => {
+                Self::TypeAnnotation
+            }
+            TokenType::Symbol(Symbol::Colon) => Self::MethodAccess,
+            TokenType::Symbol(Symbol::Typecast | Symbol::Arrow) => Self::TypeAnnotation,
+            TokenType::Keyword(Keyword::Local | Keyword::For) => Self::LocalName,
+            TokenType::Keyword(Keyword::Function) => Self::FunctionName,
+            _ => Self::Code,
+        }
+    }
+
+    /// Get the context if the position is in a comment, a string, or a number.
+    fn literal_context(store: &TokenStore, at: &TokenAt, position: Position) -> Option<Self> {
+        if at.location != TokenLocation::Token {
+            let is_in_comment = match at.trivia().map(|trivia| &trivia.trivia_type) {
+                Some(TriviaType::Comment(Comment::SingleLine(_))) => true,
+                // Right after `]]` is outside of the comment.
+                Some(TriviaType::Comment(Comment::MultiLine(_))) => store
+                    .at_position(position, Bias::Right)
+                    .is_some_and(|right| right.is_in_comment()),
+                _ => false,
+            };
+
+            return is_in_comment.then_some(Self::Comment);
+        }
+
+        let token = at.token;
+        let is_at_end = position == token.end;
+
+        match &token.token_type {
+            TokenType::Comment(Comment::SingleLine(_)) => Some(Self::Comment),
+            TokenType::Comment(Comment::MultiLine(_)) => (!is_at_end).then_some(Self::Comment),
+            TokenType::Literal(Literal::Number(_)) => Some(Self::Number),
+            TokenType::Literal(Literal::String(string)) => {
+                let is_terminated = is_terminated(string);
+                if is_at_end && is_terminated {
+                    return None;
+                }
+
+                if let LuauString::Backticks(text) = string
+                    && is_in_interpolation(text, token.start, position, store.tab_width())
+                {
+                    return Some(Self::Interpolation);
+                }
+
+                if is_terminated {
+                    Some(Self::String)
+                } else {
+                    Some(Self::UnterminatedString)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether or not completions should be shown in this context.
+    #[inline]
+    pub const fn allows_completion(self) -> bool {
+        !matches!(
+            self,
+            Self::Comment
+                | Self::String
+                | Self::UnterminatedString
+                | Self::Number
+                | Self::LocalName
+                | Self::FunctionName
+        )
+    }
+}
+
+/// Whether or not the passed string has its closing quote.
+fn is_terminated(string: &LuauString) -> bool {
+    match string {
+        LuauString::SingleQuotes(text)
+        | LuauString::DoubleQuotes(text)
+        | LuauString::Backticks(text) => {
+            let Some(body) = text.get(1..) else {
+                return false;
+            };
+            let Some(body) = body.strip_suffix(&text[..1]) else {
+                return false;
+            };
+            let backslashes = body.chars().rev().take_while(|c| *c == '\\').count();
+
+            backslashes % 2 == 0
+        }
+        LuauString::MultiLine(text) => {
+            let equals = text.chars().skip(1).take_while(|c| *c == '=').count();
+            let closing = format!("]{}]", "=".repeat(equals));
+
+            text.len() >= (equals + 2) * 2 && text.ends_with(&closing)
+        }
+    }
+}
+
+/// Whether or not the passed position is inside a `{}` in the passed backtick
+/// string, which starts at `start`.
+fn is_in_interpolation(text: &str, start: Position, position: Position, tab_width: u32) -> bool {
+    let mut current = start;
+    let mut depth = 0_usize;
+    let mut is_escaped = false;
+    let mut buffer = [0; 4];
+
+    for character in text.chars() {
+        if current >= position {
+            break;
+        }
+
+        match character {
+            _ if depth == 0 && is_escaped => is_escaped = false,
+            '\\' if depth == 0 => is_escaped = true,
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            _ => (),
+        }
+
+        current = advance_position(current, character.encode_utf8(&mut buffer), tab_width);
+    }
+
+    depth > 0
+}
+
+/// Whether or not the `:` at the passed index starts a type annotation, as
+/// opposed to a method call or definition.
+fn is_type_colon(store: &TokenStore, colon: usize) -> bool {
+    let Some(mut index) = store.previous(colon) else {
+        return false;
+    };
+
+    match store.tokens()[index].token_type {
+        // `function foo(): number` but not `foo():bar()`.
+        TokenType::Symbol(Symbol::ClosingParenthesis) => {
+            return matching_parenthesis(store, index)
+                .is_some_and(|open| is_function_parameters(store, open));
+        }
+        TokenType::Identifier(_) | TokenType::Symbol(Symbol::Ellipses) => (),
+        _ => return false,
+    }
+
+    // Go back through `a, b, c` to what's before the list.
+    loop {
+        let Some(previous) = store.previous(index) else {
+            return false;
+        };
+
+        match store.tokens()[previous].token_type {
+            TokenType::Keyword(Keyword::Local | Keyword::For) => return true,
+            TokenType::Symbol(Symbol::OpeningParenthesis) => {
+                return is_function_parameters(store, previous);
+            }
+            TokenType::Symbol(Symbol::Comma) => match store.previous(previous) {
+                Some(name)
+                    if matches!(store.tokens()[name].token_type, TokenType::Identifier(_)) =>
+                {
+                    index = name;
+                }
+                _ => return false,
+            },
+            _ => return false,
+        }
+    }
+}
+
+/// Get the index of the `(` matching the `)` at the passed index.
+fn matching_parenthesis(store: &TokenStore, close: usize) -> Option<usize> {
+    let mut depth = 0_usize;
+
+    for index in (0..close).rev() {
+        match store.tokens()[index].token_type {
+            TokenType::Symbol(Symbol::ClosingParenthesis) => depth += 1,
+            TokenType::Symbol(Symbol::OpeningParenthesis) if depth == 0 => return Some(index),
+            TokenType::Symbol(Symbol::OpeningParenthesis) => depth -= 1,
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Whether or not the `(` at the passed index starts the parameters of a
+/// function, like `function(` or `function a.b:c(`.
+fn is_function_parameters(store: &TokenStore, open: usize) -> bool {
+    let mut index = open;
+
+    loop {
+        let Some(previous) = store.previous(index) else {
+            return false;
+        };
+
+        match store.tokens()[previous].token_type {
+            TokenType::Keyword(Keyword::Function) => return true,
+            TokenType::Identifier(_) => match store.previous(previous) {
+                Some(separator)
+                    if matches!(
+                        store.tokens()[separator].token_type,
+                        TokenType::Symbol(Symbol::Dot | Symbol::Colon)
+                    ) =>
+                {
+                    index = separator;
+                }
+                Some(function) => {
+                    return store.tokens()[function].token_type
+                        == TokenType::Keyword(Keyword::Function);
+                }
+                None => return false,
+            },
+            _ => return false,
+        }
+    }
+}
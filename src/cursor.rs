@@ -0,0 +1,254 @@
+//! The [`TokenCursor`] struct and the [`TokenPattern`] trait.
+
+use std::{collections::VecDeque, mem};
+
+use crate::prelude::{
+    CompoundOperator, Error, Keyword, Lexer, LexerInput, Operator, PartialKeyword, Symbol, Token,
+    TokenKind, TokenType,
+};
+
+/// Something a token can be checked against, like a [`Symbol`] or a
+/// [`TokenKind`], for [`TokenCursor`].
+pub trait TokenPattern {
+    /// Whether or not the passed token type matches this pattern.
+    fn matches(&self, token_type: &TokenType) -> bool;
+
+    /// Describe what this pattern expects, for error messages, like `` `)` ``
+    /// or `an identifier`.
+    fn describe(&self) -> String;
+}
+
+impl<T: TokenPattern + ?Sized> TokenPattern for &T {
+    #[inline]
+    fn matches(&self, token_type: &TokenType) -> bool {
+        (**self).matches(token_type)
+    }
+
+    #[inline]
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
+
+impl TokenPattern for TokenType {
+    #[inline]
+    fn matches(&self, token_type: &TokenType) -> bool {
+        self == token_type
+    }
+
+    #[inline]
+    fn describe(&self) -> String {
+        describe(self)
+    }
+}
+
+impl TokenPattern for TokenKind {
+    #[inline]
+    fn matches(&self, token_type: &TokenType) -> bool {
+        token_type.kind() == *self
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Error => "an error",
+            Self::Number => "a number",
+            Self::String => "a string",
+            Self::Boolean => "a boolean",
+            Self::Identifier => "an identifier",
+            Self::Comment => "a comment",
+            Self::Spaces => "spaces",
+            Self::Keyword | Self::PartialKeyword => "a keyword",
+            Self::Symbol => "a symbol",
+            Self::Operator | Self::CompoundOperator => "an operator",
+            Self::EndOfFile => "the end of the file",
+        }
+        .to_string()
+    }
+}
+
+/// Implements [`TokenPattern`] for items which are wrapped by a single
+/// [`TokenType`] variant.
+macro_rules! impl_token_pattern {
+    ($($type: ident),* $(,)?) => {
+        $(
+            impl TokenPattern for $type {
+                #[inline]
+                fn matches(&self, token_type: &TokenType) -> bool {
+                    matches!(token_type, TokenType::$type(item) if item == self)
+                }
+
+                #[inline]
+                fn describe(&self) -> String {
+                    format!("`{self}`")
+                }
+            }
+        )*
+    };
+}
+
+impl_token_pattern!(Keyword, PartialKeyword, Symbol, Operator, CompoundOperator);
+
+/// Describe the passed token type, for error messages.
+fn describe(token_type: &TokenType) -> String {
+    match token_type {
+        TokenType::EndOfFile => TokenKind::EndOfFile.describe(),
+        TokenType::Error(_) => TokenKind::Error.describe(),
+        _ => token_type
+            .try_as_string()
+            .map_or_else(String::new, |text| format!("`{text}`")),
+    }
+}
+
+/// A cursor over the tokens of a [`Lexer`], for parsers. It lexes tokens as
+/// they're needed and keeps the ones looked ahead at.
+///
+/// Once the end of the file is reached, the cursor keeps returning
+/// [`TokenType::EndOfFile`] tokens.
+#[derive(Clone, Debug)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct TokenCursor<I = String> {
+    /// The lexer the tokens come from.
+    lexer: Lexer<I>,
+
+    /// The tokens which were looked ahead at but not consumed yet.
+    buffer: VecDeque<Token>,
+
+    /// Whether or not [`TokenType::Error`] tokens are skipped.
+    skip_errors: bool,
+
+    /// The errors of skipped [`TokenType::Error`] tokens.
+    errors: Vec<Error>,
+}
+
+impl<I: LexerInput> TokenCursor<I> {
+    /// Create a new [`TokenCursor`] over the passed [`Lexer`].
+    #[inline]
+    pub const fn new(lexer: Lexer<I>) -> Self {
+        Self {
+            lexer,
+            buffer: VecDeque::new(),
+            skip_errors: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Set whether or not [`TokenType::Error`] tokens are skipped, in which
+    /// case their errors are kept in [`TokenCursor::errors`]. They aren't
+    /// skipped by default. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_skip_errors(mut self, skip_errors: bool) -> Self {
+        self.skip_errors = skip_errors;
+        self
+    }
+
+    /// Get the lexer. Its position is after any token looked ahead at.
+    #[inline]
+    pub const fn lexer(&self) -> &Lexer<I> {
+        &self.lexer
+    }
+
+    /// Get the errors of the skipped [`TokenType::Error`] tokens.
+    #[inline]
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Take the errors of the skipped [`TokenType::Error`] tokens out.
+    #[inline]
+    pub fn take_errors(&mut self) -> Vec<Error> {
+        mem::take(&mut self.errors)
+    }
+
+    /// Lex the next token which isn't skipped.
+    fn lex(&mut self) -> Token {
+        loop {
+            let token = self.lexer.next_token();
+
+            match token.token_type {
+                TokenType::Error(error) if self.skip_errors => self.errors.push(error),
+                _ => return token,
+            }
+        }
+    }
+
+    /// Get the token `n` tokens ahead, `0` being the current one, without
+    /// consuming anything.
+    pub fn peek(&mut self, n: usize) -> &Token {
+        while self.buffer.len() <= n {
+            let token = self.lex();
+            self.buffer.push_back(token);
+        }
+
+        &self.buffer[n]
+    }
+
+    /// Get the current token without consuming it.
+    #[inline]
+    pub fn current(&mut self) -> &Token {
+        self.peek(0)
+    }
+
+    /// Consume the current token and return it.
+    pub fn advance(&mut self) -> Token {
+        self.buffer.pop_front().unwrap_or_else(|| self.lex())
+    }
+
+    /// Whether or not the current token is the end of the file.
+    #[inline]
+    pub fn is_at_end(&mut self) -> bool {
+        self.current().token_type == TokenType::EndOfFile
+    }
+
+    /// Whether or not the current token matches the passed pattern.
+    #[inline]
+    pub fn is_at(&mut self, pattern: impl TokenPattern) -> bool {
+        pattern.matches(&self.current().token_type)
+    }
+
+    /// Whether or not the current token matches any of the passed patterns.
+    #[inline]
+    pub fn at_any<P: TokenPattern>(&mut self, patterns: &[P]) -> bool {
+        let token_type = &self.current().token_type;
+
+        patterns.iter().any(|pattern| pattern.matches(token_type))
+    }
+
+    /// Consume the current token if it matches the passed pattern.
+    #[inline]
+    pub fn eat(&mut self, pattern: impl TokenPattern) -> Option<Token> {
+        self.is_at(pattern).then(|| self.advance())
+    }
+
+    /// Consume the current token if it matches the passed pattern, and return
+    /// an error saying what was expected otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error spanning the current token, which isn't consumed, if
+    /// it doesn't match.
+    pub fn expect(&mut self, pattern: impl TokenPattern) -> Result<Token, Error> {
+        if let Some(token) = self.eat(&pattern) {
+            return Ok(token);
+        }
+
+        let found = self.current();
+
+        Err(Error::new(
+            found.start,
+            format!(
+                "Expected {}, found {}.",
+                pattern.describe(),
+                describe(&found.token_type)
+            ),
+            Some(found.end),
+        ))
+    }
+}
+
+impl<I: LexerInput> From<Lexer<I>> for TokenCursor<I> {
+    #[inline]
+    fn from(lexer: Lexer<I>) -> Self {
+        Self::new(lexer)
+    }
+}
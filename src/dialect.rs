@@ -0,0 +1,53 @@
+//! The [`Dialect`] enum.
+
+/// The language being lexed.
+///
+/// Luau-only tokens, like compound operators and backtick strings, are only
+/// lexed in [`Dialect::Luau`], while Lua-only ones, like `goto` and bitwise
+/// operators, are only lexed in the Lua versions which have them.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Dialect {
+    /// Luau.
+    #[default]
+    Luau,
+
+    /// Lua 5.1.
+    Lua51,
+
+    /// Lua 5.2, adds `goto` and `::label::`.
+    Lua52,
+
+    /// Lua 5.3, adds `//` and the bitwise operators `&`, `|`, `~`, `<<`, and `>>`.
+    Lua53,
+
+    /// Lua 5.4, adds the `<const>` and `<close>` attributes.
+    Lua54,
+}
+
+impl Dialect {
+    /// Whether or not this is [`Dialect::Luau`].
+    #[inline]
+    pub const fn is_luau(self) -> bool {
+        matches!(self, Self::Luau)
+    }
+
+    /// Whether or not this dialect has `goto` and `::label::`.
+    #[inline]
+    pub const fn has_labels(self) -> bool {
+        matches!(self, Self::Lua52 | Self::Lua53 | Self::Lua54)
+    }
+
+    /// Whether or not this dialect has the `&`, `|`, `~`, `<<`, and `>>` bitwise
+    /// operators.
+    #[inline]
+    pub const fn has_bitwise_operators(self) -> bool {
+        matches!(self, Self::Lua53 | Self::Lua54)
+    }
+
+    /// Whether or not this dialect has the `//` operator.
+    #[inline]
+    pub const fn has_floor_division(self) -> bool {
+        matches!(self, Self::Luau | Self::Lua53 | Self::Lua54)
+    }
+}
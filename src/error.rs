@@ -15,6 +15,21 @@ pub struct Error {
 
     /// The ending location of the error.
     end: Option<Position>,
+
+    /// How severe the error is.
+    severity: Severity,
+}
+
+/// How severe an [`Error`] is.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Severity {
+    /// The code is invalid.
+    #[default]
+    Error,
+
+    /// The code is valid but might not behave as intended.
+    Warning,
 }
 
 impl Error {
@@ -25,9 +40,18 @@ impl Error {
             start,
             message: message.into(),
             end,
+            severity: Severity::Error,
         }
     }
 
+    /// Set the error's [`Severity`]. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
     /// Get the start of the error.
     #[inline]
     pub const fn start(&self) -> Position {
@@ -45,4 +69,16 @@ impl Error {
     pub const fn end(&self) -> Option<Position> {
         self.end
     }
+
+    /// Get the error's [`Severity`].
+    #[inline]
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Whether or not this is a [`Severity::Warning`].
+    #[inline]
+    pub const fn is_warning(&self) -> bool {
+        matches!(self.severity, Severity::Warning)
+    }
 }
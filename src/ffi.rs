@@ -0,0 +1,290 @@
+//! A C interface to the lexer, behind the `ffi` feature. The matching header is
+//! `include/luau_lexer.h`.
+//!
+//! The lexer is an opaque [`LuauLexer`] handle created by [`luau_lexer_new`] and
+//! freed by [`luau_lexer_free`]. Tokens are returned one at a time as flat
+//! [`LuauToken`]s by [`luau_lexer_next_token`], their texts point into the
+//! lexer's copy of the code and stay valid until it's freed.
+
+use std::{ffi::c_char, ptr, slice, str};
+
+use crate::prelude::{Error, Lexer, TokenType};
+
+/// [`TokenKind::Error`](crate::token::TokenKind::Error)
+pub const LUAU_TOKEN_ERROR: u32 = 0;
+/// [`TokenKind::Number`](crate::token::TokenKind::Number)
+pub const LUAU_TOKEN_NUMBER: u32 = 1;
+/// [`TokenKind::String`](crate::token::TokenKind::String)
+pub const LUAU_TOKEN_STRING: u32 = 2;
+/// [`TokenKind::Boolean`](crate::token::TokenKind::Boolean)
+pub const LUAU_TOKEN_BOOLEAN: u32 = 3;
+/// [`TokenKind::Identifier`](crate::token::TokenKind::Identifier)
+pub const LUAU_TOKEN_IDENTIFIER: u32 = 4;
+/// [`TokenKind::Comment`](crate::token::TokenKind::Comment)
+pub const LUAU_TOKEN_COMMENT: u32 = 5;
+/// [`TokenKind::Keyword`](crate::token::TokenKind::Keyword)
+pub const LUAU_TOKEN_KEYWORD: u32 = 6;
+/// [`TokenKind::PartialKeyword`](crate::token::TokenKind::PartialKeyword)
+pub const LUAU_TOKEN_PARTIAL_KEYWORD: u32 = 7;
+/// [`TokenKind::Symbol`](crate::token::TokenKind::Symbol)
+pub const LUAU_TOKEN_SYMBOL: u32 = 8;
+/// [`TokenKind::Operator`](crate::token::TokenKind::Operator)
+pub const LUAU_TOKEN_OPERATOR: u32 = 9;
+/// [`TokenKind::CompoundOperator`](crate::token::TokenKind::CompoundOperator)
+pub const LUAU_TOKEN_COMPOUND_OPERATOR: u32 = 10;
+/// [`TokenKind::EndOfFile`](crate::token::TokenKind::EndOfFile)
+pub const LUAU_TOKEN_END_OF_FILE: u32 = 11;
+/// [`TokenKind::Spaces`](crate::token::TokenKind::Spaces)
+pub const LUAU_TOKEN_SPACES: u32 = 12;
+
+/// [`LuauToken::error_index`] of tokens which aren't errors.
+pub const LUAU_NO_ERROR: usize = usize::MAX;
+
+/// An opaque lexer handle.
+#[derive(Debug)]
+pub struct LuauLexer {
+    /// The lexer, which owns a copy of the code.
+    lexer: Lexer,
+
+    /// The errors returned so far. They're boxed so the messages, which may be
+    /// stored inline, don't move when more are added.
+    #[allow(clippy::vec_box)] // The boxes are needed for stable addresses.
+    errors: Vec<Box<Error>>,
+
+    /// Whether or not [`TokenType::EndOfFile`] was returned.
+    is_done: bool,
+}
+
+/// A token, without its trivia.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct LuauToken {
+    /// One of the `LUAU_TOKEN_*` constants.
+    pub kind: u32,
+
+    /// The zero-based line the token starts at.
+    pub start_line: u32,
+
+    /// The zero-based column the token starts at, in characters.
+    pub start_column: u32,
+
+    /// The zero-based line the token ends at.
+    pub end_line: u32,
+
+    /// The zero-based column the token ends at, exclusive.
+    pub end_column: u32,
+
+    /// The byte offset the token starts at.
+    pub start_offset: usize,
+
+    /// The byte offset the token ends at, exclusive.
+    pub end_offset: usize,
+
+    /// The text of the token, which isn't null-terminated.
+    pub text: *const c_char,
+
+    /// The length of `text` in bytes.
+    pub text_length: usize,
+
+    /// The index of the error for [`luau_lexer_error`], or [`LUAU_NO_ERROR`].
+    pub error_index: usize,
+}
+
+/// An error met while lexing.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct LuauError {
+    /// The error message, which isn't null-terminated.
+    pub message: *const c_char,
+
+    /// The length of `message` in bytes.
+    pub message_length: usize,
+
+    /// The zero-based line the error starts at.
+    pub start_line: u32,
+
+    /// The zero-based column the error starts at, in characters.
+    pub start_column: u32,
+
+    /// The zero-based line the error ends at.
+    pub end_line: u32,
+
+    /// The zero-based column the error ends at, exclusive.
+    pub end_column: u32,
+
+    /// Whether or not the error is only a warning.
+    pub is_warning: bool,
+}
+
+/// Create a lexer over a copy of the passed UTF-8 code.
+///
+/// Returns null if the code isn't valid UTF-8.
+///
+/// # Safety
+///
+/// `code` must point to `length` readable bytes, it can only be null if
+/// `length` is `0`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn luau_lexer_new(code: *const c_char, length: usize) -> *mut LuauLexer {
+    let bytes = if length == 0 {
+        &[]
+    } else if code.is_null() {
+        return ptr::null_mut();
+    } else {
+        // SAFETY: The caller guarantees `code` points to `length` bytes.
+        unsafe { slice::from_raw_parts(code.cast::<u8>(), length) }
+    };
+
+    let Ok(code) = str::from_utf8(bytes) else {
+        return ptr::null_mut();
+    };
+
+    Box::into_raw(Box::new(LuauLexer {
+        lexer: Lexer::new(code),
+        errors: Vec::new(),
+        is_done: false,
+    }))
+}
+
+/// Free a lexer created by [`luau_lexer_new`], invalidating all texts it
+/// returned. Passing null does nothing.
+///
+/// # Safety
+///
+/// `lexer` must be null or returned by [`luau_lexer_new`] and not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn luau_lexer_free(lexer: *mut LuauLexer) {
+    if !lexer.is_null() {
+        // SAFETY: The caller guarantees `lexer` came from `Box::into_raw`.
+        drop(unsafe { Box::from_raw(lexer) });
+    }
+}
+
+/// Lex the next token into `token`.
+///
+/// Returns false, without writing anything, once the end of file token was
+/// returned or if either pointer is null.
+///
+/// # Safety
+///
+/// `lexer` must be null or a live lexer, and `token` must be null or valid for
+/// writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn luau_lexer_next_token(
+    lexer: *mut LuauLexer,
+    token: *mut LuauToken,
+) -> bool {
+    // SAFETY: The caller guarantees `lexer` is null or live.
+    let Some(lexer) = (unsafe { lexer.as_mut() }) else {
+        return false;
+    };
+    if lexer.is_done || token.is_null() {
+        return false;
+    }
+
+    let start_offset = lexer.lexer.offset();
+    let lexed = lexer.lexer.next_token();
+
+    // Zero-width tokens may still move the lexer, like when it stops after too
+    // many errors.
+    let end_offset = if lexed.start == lexed.end {
+        start_offset
+    } else {
+        lexed
+            .trailing_trivia
+            .first()
+            .map_or_else(|| lexer.lexer.offset(), |trivia| trivia.start_offset)
+    };
+
+    let kind = lexed.token_type.kind() as u32;
+    let error_index = match lexed.token_type {
+        TokenType::Error(error) => {
+            lexer.errors.push(Box::new(error));
+            lexer.errors.len() - 1
+        }
+        TokenType::EndOfFile => {
+            lexer.is_done = true;
+            LUAU_NO_ERROR
+        }
+        _ => LUAU_NO_ERROR,
+    };
+
+    let text = lexer
+        .lexer
+        .input()
+        .get(start_offset..end_offset)
+        .unwrap_or_default();
+
+    // SAFETY: The caller guarantees `token` is valid for writes.
+    unsafe {
+        token.write(LuauToken {
+            kind,
+            start_line: lexed.start.line,
+            start_column: lexed.start.character,
+            end_line: lexed.end.line,
+            end_column: lexed.end.character,
+            start_offset,
+            end_offset,
+            text: text.as_ptr().cast(),
+            text_length: text.len(),
+            error_index,
+        });
+    }
+
+    true
+}
+
+/// Get the number of errors returned so far.
+///
+/// # Safety
+///
+/// `lexer` must be null or a live lexer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn luau_lexer_error_count(lexer: *const LuauLexer) -> usize {
+    // SAFETY: The caller guarantees `lexer` is null or live.
+    unsafe { lexer.as_ref() }.map_or(0, |lexer| lexer.errors.len())
+}
+
+/// Get the error at the passed index into `error`. Its message stays valid
+/// until the lexer is freed.
+///
+/// Returns false, without writing anything, if the index is out of bounds or
+/// if either pointer is null.
+///
+/// # Safety
+///
+/// `lexer` must be null or a live lexer, and `error` must be null or valid for
+/// writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn luau_lexer_error(
+    lexer: *const LuauLexer,
+    index: usize,
+    error: *mut LuauError,
+) -> bool {
+    // SAFETY: The caller guarantees `lexer` is null or live.
+    let Some(lexer) = (unsafe { lexer.as_ref() }) else {
+        return false;
+    };
+    let (Some(found), false) = (lexer.errors.get(index), error.is_null()) else {
+        return false;
+    };
+
+    let start = found.start();
+    let end = found.end().unwrap_or(start);
+    let message = found.message();
+
+    // SAFETY: The caller guarantees `error` is valid for writes.
+    unsafe {
+        error.write(LuauError {
+            message: message.as_ptr().cast(),
+            message_length: message.len(),
+            start_line: start.line,
+            start_column: start.character,
+            end_line: end.line,
+            end_column: end.character,
+            is_warning: found.is_warning(),
+        });
+    }
+
+    true
+}
@@ -0,0 +1,288 @@
+//! The [`folding_ranges`] function.
+
+use lsp_types::{FoldingRange, FoldingRangeKind, Position};
+
+use crate::prelude::{
+    Comment, GroupKind, Keyword, Literal, LuauString, Symbol, Token, TokenNode, TokenTree,
+    TokenType, Trivia, TriviaType,
+};
+
+/// Get the folding ranges of the passed tokens.
+///
+/// This covers blocks, multi-line tables, argument lists and strings, block
+/// comments, runs of single line comments, the run of
+/// `local x = require(...)` statements at the start of the file, and
+/// `--#region` / `--#endregion` markers.
+///
+/// Blocks, tables, and argument lists are folded up to the line before their
+/// closing token, so it stays visible. Unclosed ones are folded up to their
+/// last token, as folding doesn't need the code to be valid.
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub fn folding_ranges(tokens: &[Token]) -> Vec<FoldingRange> {
+    let mut ranges = Vec::new();
+    let tree = TokenTree::new(tokens);
+
+    fold_groups(&tree.nodes, &mut ranges);
+    fold_requires(&tree.nodes, &mut ranges);
+    fold_strings(tokens, &mut ranges);
+    fold_comments(tokens, &mut ranges);
+
+    ranges.sort_by_key(|range| (range.start_line, range.end_line));
+    ranges
+}
+
+/// Create a [`FoldingRange`] if it spans more than one line.
+fn push_range(
+    ranges: &mut Vec<FoldingRange>,
+    start_line: u32,
+    end_line: u32,
+    kind: Option<FoldingRangeKind>,
+) {
+    if end_line > start_line {
+        ranges.push(FoldingRange {
+            start_line,
+            end_line,
+            kind,
+            ..Default::default()
+        });
+    }
+}
+
+/// Fold all groups in the passed nodes.
+fn fold_groups(nodes: &[TokenNode], ranges: &mut Vec<FoldingRange>) {
+    for node in nodes {
+        let TokenNode::Group(group) = node else {
+            continue;
+        };
+
+        let end_line = group.close.map_or_else(
+            || last_line(&group.children).unwrap_or(group.open.end.line),
+            |close| close.start.line.saturating_sub(1),
+        );
+        push_range(ranges, group.open.end.line, end_line, None);
+
+        fold_groups(&group.children, ranges);
+    }
+}
+
+/// The last line of the last token in the passed nodes.
+fn last_line(nodes: &[TokenNode]) -> Option<u32> {
+    match nodes.last()? {
+        TokenNode::Token(token) => Some(token.end.line),
+        TokenNode::Group(group) => Some(group.close.map_or_else(
+            || last_line(&group.children).unwrap_or(group.open.end.line),
+            |close| close.end.line,
+        )),
+    }
+}
+
+/// Fold the run of `local x = require(...)` statements at the start of the
+/// file. Requires after any other top-level statement aren't folded.
+fn fold_requires(nodes: &[TokenNode], ranges: &mut Vec<FoldingRange>) {
+    let mut index = 0;
+    let mut run: Option<(u32, u32)> = None;
+
+    while index < nodes.len() {
+        // Comments lexed as tokens don't end the run.
+        if let TokenNode::Token(token) = &nodes[index]
+            && matches!(token.token_type, TokenType::Comment(_))
+        {
+            index += 1;
+            continue;
+        }
+
+        let Some((next, start_line, end_line)) = match_require(nodes, index) else {
+            break;
+        };
+
+        run = Some(run.map_or((start_line, end_line), |(start, _)| (start, end_line)));
+        index = next;
+    }
+
+    if let Some((start_line, end_line)) = run {
+        push_range(
+            ranges,
+            start_line,
+            end_line,
+            Some(FoldingRangeKind::Imports),
+        );
+    }
+}
+
+/// Match a `local x = require(...)` statement starting at the passed index,
+/// returning the index after it and the lines it spans.
+fn match_require(nodes: &[TokenNode], index: usize) -> Option<(usize, u32, u32)> {
+    /// Get the token at the passed index, if it's not a group.
+    fn token<'a>(nodes: &[TokenNode<'a>], index: usize) -> Option<&'a Token> {
+        match nodes.get(index)? {
+            TokenNode::Token(token) => Some(token),
+            TokenNode::Group(_) => None,
+        }
+    }
+
+    let local =
+        token(nodes, index).filter(|token| **token == TokenType::Keyword(Keyword::Local))?;
+    token(nodes, index + 1).filter(|token| matches!(token.token_type, TokenType::Identifier(_)))?;
+    token(nodes, index + 2).filter(|token| **token == TokenType::Symbol(Symbol::Equal))?;
+    token(nodes, index + 3).filter(
+        |token| matches!(&token.token_type, TokenType::Identifier(name) if name == "require"),
+    )?;
+
+    let mut end_line = match nodes.get(index + 4)? {
+        TokenNode::Group(group) if group.kind == GroupKind::Parentheses => group.close?.end.line,
+        TokenNode::Token(token)
+            if matches!(token.token_type, TokenType::Literal(Literal::String(_))) =>
+        {
+            token.end.line
+        }
+        _ => return None,
+    };
+    let mut next = index + 5;
+
+    // `require(...).Field`
+    while let (Some(dot), Some(field)) = (token(nodes, next), token(nodes, next + 1)) {
+        if *dot != TokenType::Symbol(Symbol::Dot)
+            || !matches!(field.token_type, TokenType::Identifier(_))
+        {
+            break;
+        }
+
+        end_line = field.end.line;
+        next += 2;
+    }
+
+    if let Some(semicolon) = token(nodes, next)
+        && *semicolon == TokenType::Symbol(Symbol::Semicolon)
+    {
+        next += 1;
+    }
+
+    Some((next, local.start.line, end_line))
+}
+
+/// Fold multi-line strings.
+fn fold_strings(tokens: &[Token], ranges: &mut Vec<FoldingRange>) {
+    for token in tokens {
+        if matches!(
+            token.token_type,
+            TokenType::Literal(Literal::String(LuauString::MultiLine(_)))
+        ) {
+            push_range(ranges, token.start.line, token.end.line, None);
+        }
+    }
+}
+
+/// Fold block comments, runs of single line comments, and regions.
+fn fold_comments(tokens: &[Token], ranges: &mut Vec<FoldingRange>) {
+    let mut folder = CommentFolder {
+        ranges,
+        regions: Vec::new(),
+        run: None,
+        code_line: None,
+    };
+
+    if let Some(first) = tokens.first() {
+        for trivia in &first.leading_trivia {
+            folder.trivia(trivia);
+        }
+    }
+
+    for token in tokens {
+        if let TokenType::Comment(comment) = &token.token_type {
+            folder.comment(comment, token.start, token.end);
+        } else if token.start != token.end && !matches!(token.token_type, TokenType::Spaces(_)) {
+            folder.run_ended();
+            folder.code_line = Some(token.end.line);
+        }
+
+        for trivia in &token.trailing_trivia {
+            folder.trivia(trivia);
+        }
+    }
+
+    folder.run_ended();
+}
+
+/// Keeps track of comments being folded.
+struct CommentFolder<'a> {
+    /// The ranges to push to.
+    ranges: &'a mut Vec<FoldingRange>,
+
+    /// The lines of the `--#region` comments which weren't closed yet.
+    regions: Vec<u32>,
+
+    /// The first and last lines of the current run of single line comments.
+    run: Option<(u32, u32)>,
+
+    /// The last line with code on it.
+    code_line: Option<u32>,
+}
+
+impl CommentFolder<'_> {
+    /// Handle the passed trivia.
+    fn trivia(&mut self, trivia: &Trivia) {
+        if let TriviaType::Comment(comment) = &trivia.trivia_type {
+            self.comment(comment, trivia.start, trivia.end);
+        }
+    }
+
+    /// Handle the passed comment.
+    fn comment(&mut self, comment: &Comment, start: Position, end: Position) {
+        let text = comment.as_str();
+
+        if let Some(name) = text.strip_prefix("--#") {
+            if name.starts_with("region") {
+                self.run_ended();
+                self.regions.push(start.line);
+
+                return;
+            }
+            if name.starts_with("endregion") {
+                self.run_ended();
+                if let Some(start_line) = self.regions.pop() {
+                    push_range(
+                        self.ranges,
+                        start_line,
+                        end.line,
+                        Some(FoldingRangeKind::Region),
+                    );
+                }
+
+                return;
+            }
+        }
+
+        match comment {
+            Comment::MultiLine(_) => {
+                self.run_ended();
+                push_range(
+                    self.ranges,
+                    start.line,
+                    end.line,
+                    Some(FoldingRangeKind::Comment),
+                );
+            }
+            // Comments after code are never part of a run.
+            Comment::SingleLine(_) if self.code_line == Some(start.line) => self.run_ended(),
+            Comment::SingleLine(_) => match &mut self.run {
+                Some((_, last_line)) if *last_line + 1 == start.line => *last_line = start.line,
+                _ => {
+                    self.run_ended();
+                    self.run = Some((start.line, start.line));
+                }
+            },
+        }
+    }
+
+    /// Fold the current run of single line comments, if any.
+    fn run_ended(&mut self) {
+        if let Some((start_line, end_line)) = self.run.take() {
+            push_range(
+                self.ranges,
+                start_line,
+                end_line,
+                Some(FoldingRangeKind::Comment),
+            );
+        }
+    }
+}
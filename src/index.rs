@@ -0,0 +1,234 @@
+//! The [`IdentifierIndex`] struct.
+
+use lsp_types::{
+    DocumentHighlight, DocumentHighlightKind, Position, Range, TextEdit, Uri, WorkspaceEdit,
+};
+use smol_str::SmolStr;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    prelude::{CompoundOperator, Error, Keyword, PositionExt, Symbol, Token, TokenType},
+    utils::{can_be_identifier, is_identifier_start},
+};
+
+/// All identifiers in a file, grouped by name.
+///
+/// This is purely lexical: it doesn't know about scopes, so a local and a
+/// field with the same name are the same identifier, and identifiers inside
+/// interpolated strings are missing. It's meant as a fallback when semantic
+/// analysis isn't possible.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct IdentifierIndex {
+    /// The occurrences of each name, in order.
+    names: BTreeMap<SmolStr, Vec<Occurrence>>,
+
+    /// All occurrences in order, with their names.
+    occurrences: Vec<(Occurrence, SmolStr)>,
+}
+
+/// A single occurrence of an identifier.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Occurrence {
+    /// Where the identifier is.
+    pub range: Range,
+
+    /// Whether or not this occurrence is assigned to, like `x` in `local x`
+    /// and `x = 1`.
+    pub is_write: bool,
+}
+
+impl IdentifierIndex {
+    /// Index the identifiers in the passed tokens. [`TokenType::PartialKeyword`]s
+    /// are included when they're used as identifiers, like `type` in
+    /// `local type = 1` or `typeof` in `typeof(x)`.
+    pub fn new(tokens: &[Token]) -> Self {
+        let mut index = Self::default();
+        let significant = tokens
+            .iter()
+            .filter(|token| match token.token_type {
+                TokenType::Comment(_) | TokenType::Spaces(_) => false,
+                TokenType::Error(_) => token.start != token.end,
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+
+        for (i, token) in significant.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|i| &significant[i].token_type);
+            let next = significant.get(i + 1).map(|token| &token.token_type);
+
+            let name = match &token.token_type {
+                TokenType::Identifier(name) => name.clone(),
+                TokenType::PartialKeyword(keyword) if is_used_as_identifier(previous, next) => {
+                    SmolStr::new(keyword.to_string())
+                }
+                _ => continue,
+            };
+
+            let occurrence = Occurrence {
+                range: Range::new(token.start, token.end),
+                is_write: is_write(previous, next),
+            };
+
+            index
+                .names
+                .entry(name.clone())
+                .or_default()
+                .push(occurrence);
+            index.occurrences.push((occurrence, name));
+        }
+
+        index
+    }
+
+    /// Get all indexed names.
+    #[inline]
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.keys().map(SmolStr::as_str)
+    }
+
+    /// Get all occurrences of the passed name.
+    #[inline]
+    pub fn occurrences(&self, name: &str) -> &[Occurrence] {
+        self.names.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the name of the identifier at the passed position. Being right
+    /// before or after an identifier counts as being on it.
+    pub fn name_at(&self, position: Position) -> Option<&str> {
+        // Occurrences are in order so the first one which ends after the
+        // position is the only one which may contain it.
+        let index = self
+            .occurrences
+            .partition_point(|(occurrence, _)| occurrence.range.end < position);
+        let (occurrence, name) = self.occurrences.get(index)?;
+
+        position
+            .is_in_bounds(occurrence.range.start, occurrence.range.end)
+            .then_some(name.as_str())
+    }
+
+    /// Get the `textDocument/documentHighlight` result for the passed position,
+    /// which is every occurrence of the identifier there.
+    pub fn highlights(&self, position: Position) -> Vec<DocumentHighlight> {
+        let Some(name) = self.name_at(position) else {
+            return Vec::new();
+        };
+
+        self.occurrences(name)
+            .iter()
+            .map(|occurrence| DocumentHighlight {
+                range: occurrence.range,
+                kind: Some(if occurrence.is_write {
+                    DocumentHighlightKind::WRITE
+                } else {
+                    DocumentHighlightKind::READ
+                }),
+            })
+            .collect()
+    }
+
+    /// Get the edits renaming every occurrence of `name` to `new_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `new_name` isn't a valid identifier or is a keyword.
+    pub fn rename(&self, name: &str, new_name: &str) -> Result<Vec<TextEdit>, Error> {
+        Self::rename_ranges(
+            self.occurrences(name)
+                .iter()
+                .map(|occurrence| occurrence.range),
+            new_name,
+        )
+    }
+
+    /// Get the edits renaming the passed ranges, usually a subset of the
+    /// [`occurrences`](Self::occurrences) of a name, to `new_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, placed at the first range, if `new_name` isn't a valid
+    /// identifier or is a keyword.
+    pub fn rename_ranges(
+        ranges: impl IntoIterator<Item = Range>,
+        new_name: &str,
+    ) -> Result<Vec<TextEdit>, Error> {
+        let edits = ranges
+            .into_iter()
+            .map(|range| TextEdit::new(range, new_name.to_string()))
+            .collect::<Vec<_>>();
+
+        let message = if !new_name.starts_with(is_identifier_start)
+            || !new_name.chars().all(can_be_identifier)
+        {
+            format!("`{new_name}` isn't a valid identifier.")
+        } else if is_reserved(new_name) {
+            format!("`{new_name}` is a keyword.")
+        } else {
+            return Ok(edits);
+        };
+
+        let range = edits.first().map(|edit| edit.range).unwrap_or_default();
+        Err(Error::new(range.start, message, Some(range.end)))
+    }
+
+    /// Like [`IdentifierIndex::rename`] but returns a [`WorkspaceEdit`] for the
+    /// document at the passed uri.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `new_name` isn't a valid identifier or is a keyword.
+    pub fn rename_workspace_edit(
+        &self,
+        uri: Uri,
+        name: &str,
+        new_name: &str,
+    ) -> Result<WorkspaceEdit, Error> {
+        let edits = self.rename(name, new_name)?;
+
+        Ok(WorkspaceEdit::new(HashMap::from([(uri, edits)])))
+    }
+}
+
+/// Whether or not the passed name can't be used as an identifier.
+fn is_reserved(name: &str) -> bool {
+    // `true`, `false`, `and`, `or`, and `not` are lexed as literals and
+    // operators but are still reserved.
+    Keyword::try_from_str(name).is_some() || matches!(name, "true" | "false" | "and" | "or" | "not")
+}
+
+/// Whether or not a partial keyword between the passed tokens is used as an
+/// identifier instead of a keyword.
+const fn is_used_as_identifier(previous: Option<&TokenType>, next: Option<&TokenType>) -> bool {
+    matches!(
+        previous,
+        Some(
+            TokenType::Symbol(Symbol::Dot | Symbol::Colon)
+                | TokenType::Keyword(Keyword::Local | Keyword::Function)
+        )
+    ) || matches!(
+        next,
+        Some(TokenType::Symbol(_) | TokenType::Operator(_) | TokenType::CompoundOperator(_))
+    )
+}
+
+/// Whether or not an identifier between the passed tokens is assigned to.
+const fn is_write(previous: Option<&TokenType>, next: Option<&TokenType>) -> bool {
+    match next {
+        Some(TokenType::Symbol(Symbol::Equal)) => return true,
+        Some(TokenType::CompoundOperator(
+            CompoundOperator::EqualEqual
+            | CompoundOperator::LessThanOrEqualTo
+            | CompoundOperator::GreaterThanOrEqualTo,
+        )) => (),
+        Some(TokenType::CompoundOperator(_)) => return true,
+        _ => (),
+    }
+
+    matches!(
+        previous,
+        Some(TokenType::Keyword(
+            Keyword::Local | Keyword::Function | Keyword::For
+        ))
+    )
+}
@@ -0,0 +1,277 @@
+//! The [`LexerInput`] trait and its implementations.
+
+use std::{borrow::Cow, ops::Range};
+
+/// A source of text for the [`Lexer`](crate::lexer::Lexer). All offsets are in
+/// bytes and the lexer only ever passes offsets which are at character
+/// boundaries.
+#[allow(clippy::module_name_repetitions)] // Wouldn't make much sense otherwise.
+pub trait LexerInput {
+    /// The length of the input, in bytes.
+    fn len(&self) -> usize;
+
+    /// Whether or not the input is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the byte at the passed offset.
+    fn byte_at(&self, offset: usize) -> Option<u8>;
+
+    /// Get the character starting at the passed offset.
+    fn char_at(&self, offset: usize) -> Option<char>;
+
+    /// Get the text in the passed range.
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str>;
+
+    /// Get the offset of the first byte, at or after the passed offset, which
+    /// is one of the passed bytes. The lexer uses this to skip to the end of
+    /// comments and strings, so inputs should override it with a faster search
+    /// when they can.
+    fn find_byte(&self, offset: usize, bytes: &[u8]) -> Option<usize> {
+        (offset..self.len()).find(|offset| {
+            self.byte_at(*offset)
+                .is_some_and(|byte| bytes.contains(&byte))
+        })
+    }
+}
+
+/// Find the first of the passed bytes in the haystack, using `memchr` for up to
+/// three bytes.
+fn find_in(haystack: &[u8], bytes: &[u8]) -> Option<usize> {
+    match *bytes {
+        [byte] => memchr::memchr(byte, haystack),
+        [first, second] => memchr::memchr2(first, second, haystack),
+        [first, second, third] => memchr::memchr3(first, second, third, haystack),
+        _ => haystack.iter().position(|byte| bytes.contains(byte)),
+    }
+}
+
+impl LexerInput for str {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn byte_at(&self, offset: usize) -> Option<u8> {
+        self.as_bytes().get(offset).copied()
+    }
+
+    #[inline]
+    fn char_at(&self, offset: usize) -> Option<char> {
+        self.get(offset..)?.chars().next()
+    }
+
+    #[inline]
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Borrowed(&self[range])
+    }
+
+    #[inline]
+    fn find_byte(&self, offset: usize, bytes: &[u8]) -> Option<usize> {
+        find_in(self.as_bytes().get(offset..)?, bytes).map(|found| offset + found)
+    }
+}
+
+impl LexerInput for String {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn byte_at(&self, offset: usize) -> Option<u8> {
+        self.as_str().byte_at(offset)
+    }
+
+    #[inline]
+    fn char_at(&self, offset: usize) -> Option<char> {
+        self.as_str().char_at(offset)
+    }
+
+    #[inline]
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Borrowed(&self[range])
+    }
+
+    #[inline]
+    fn find_byte(&self, offset: usize, bytes: &[u8]) -> Option<usize> {
+        self.as_str().find_byte(offset, bytes)
+    }
+}
+
+impl<T: LexerInput + ?Sized> LexerInput for &T {
+    #[inline]
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    #[inline]
+    fn byte_at(&self, offset: usize) -> Option<u8> {
+        (**self).byte_at(offset)
+    }
+
+    #[inline]
+    fn char_at(&self, offset: usize) -> Option<char> {
+        (**self).char_at(offset)
+    }
+
+    #[inline]
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        (**self).slice(range)
+    }
+
+    #[inline]
+    fn find_byte(&self, offset: usize, bytes: &[u8]) -> Option<usize> {
+        (**self).find_byte(offset, bytes)
+    }
+}
+
+/// An input made of multiple chunks of text, like a rope or a file being read
+/// in parts. Chunks are never merged, so tokens spanning multiple chunks are
+/// the only ones which allocate when sliced.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[allow(clippy::module_name_repetitions)] // Wouldn't make much sense otherwise.
+pub struct ChunkedInput {
+    /// The chunks, none of them is empty.
+    chunks: Vec<String>,
+
+    /// The offset at which each chunk starts.
+    starts: Vec<usize>,
+
+    /// The total length of all chunks.
+    len: usize,
+}
+
+impl ChunkedInput {
+    /// Create a new, empty, [`ChunkedInput`].
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            chunks: Vec::new(),
+            starts: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Add a chunk to the end of the input. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub fn with_chunk(mut self, chunk: impl Into<String>) -> Self {
+        self.push(chunk);
+        self
+    }
+
+    /// Add a chunk to the end of the input.
+    pub fn push(&mut self, chunk: impl Into<String>) {
+        let chunk = chunk.into();
+        if chunk.is_empty() {
+            return;
+        }
+
+        self.starts.push(self.len);
+        self.len += chunk.len();
+        self.chunks.push(chunk);
+    }
+
+    /// Get the chunks of this input.
+    #[inline]
+    pub fn chunks(&self) -> &[String] {
+        &self.chunks
+    }
+
+    /// Get the index of the chunk containing the passed offset, and the offset
+    /// relative to that chunk.
+    fn locate(&self, offset: usize) -> Option<(usize, usize)> {
+        if offset >= self.len {
+            return None;
+        }
+
+        let index = self.starts.partition_point(|start| *start <= offset) - 1;
+
+        Some((index, offset - self.starts[index]))
+    }
+}
+
+impl<T: Into<String>> FromIterator<T> for ChunkedInput {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::new(), Self::with_chunk)
+    }
+}
+
+impl From<&str> for ChunkedInput {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::new().with_chunk(value)
+    }
+}
+
+impl LexerInput for ChunkedInput {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn byte_at(&self, offset: usize) -> Option<u8> {
+        let (index, offset) = self.locate(offset)?;
+
+        self.chunks[index].as_bytes().get(offset).copied()
+    }
+
+    #[inline]
+    fn char_at(&self, offset: usize) -> Option<char> {
+        let (index, offset) = self.locate(offset)?;
+
+        self.chunks[index].get(offset..)?.chars().next()
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        if range.is_empty() {
+            return Cow::Borrowed("");
+        }
+
+        let Some((start_index, start)) = self.locate(range.start) else {
+            return Cow::Borrowed("");
+        };
+        let end = range.end - self.starts[start_index];
+
+        if let Some(text) = self.chunks[start_index].get(start..end) {
+            return Cow::Borrowed(text);
+        }
+
+        let mut text = String::with_capacity(range.len());
+        let mut offset = range.start;
+
+        while offset < range.end {
+            let Some((index, start)) = self.locate(offset) else {
+                break;
+            };
+            let chunk = &self.chunks[index];
+            let end = chunk.len().min(start + range.end - offset);
+
+            text.push_str(&chunk[start..end]);
+            offset += end - start;
+        }
+
+        Cow::Owned(text)
+    }
+
+    #[inline]
+    fn find_byte(&self, offset: usize, bytes: &[u8]) -> Option<usize> {
+        let (index, start) = self.locate(offset)?;
+
+        self.chunks[index..]
+            .iter()
+            .zip(&self.starts[index..])
+            .enumerate()
+            .find_map(|(i, (chunk, chunk_start))| {
+                let start = if i == 0 { start } else { 0 };
+
+                find_in(&chunk.as_bytes()[start..], bytes).map(|found| chunk_start + start + found)
+            })
+    }
+}
@@ -0,0 +1,176 @@
+//! The [`Interner`] struct.
+
+use smol_str::SmolStr;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    hash::{Hash, Hasher},
+};
+
+/// A compact id for a string in an [`Interner`]. Ids are only meaningful for
+/// the interner which created them.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct StringId(u32);
+
+impl StringId {
+    /// Get the index of this id, ids are given in order starting from `0`.
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Stores each distinct string once and gives it a [`StringId`].
+///
+/// When a [`Lexer`](crate::lexer::Lexer) has an interner, the texts of all
+/// identifiers and strings it lexes go through it, so tokens with the same
+/// text share a single allocation, and their ids are available through
+/// [`Lexer::string_id`](crate::lexer::Lexer::string_id). The same interner can
+/// be moved from lexer to lexer to share strings, and ids, between all files
+/// of a workspace.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(from = "Vec<SmolStr>", into = "Vec<SmolStr>")
+)]
+pub struct Interner {
+    /// The id of each string.
+    ids: HashMap<SmolStr, StringId>,
+
+    /// The strings, indexed by their ids.
+    strings: Vec<SmolStr>,
+}
+
+impl Interner {
+    /// Create a new, empty, [`Interner`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the id of the passed string, adding it if it's new.
+    #[inline]
+    pub fn intern(&mut self, text: &str) -> StringId {
+        self.intern_str(text).0
+    }
+
+    /// Get the id of the passed string, adding it if it's new, and the stored
+    /// copy of it, which shares its allocation with all other copies. The
+    /// string is only copied if it's new.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`u32::MAX`] distinct strings.
+    #[inline]
+    pub fn intern_str(&mut self, text: &str) -> (StringId, SmolStr) {
+        self.intern_with(text, || SmolStr::new(text))
+    }
+
+    /// Same as [`Interner::intern_str`], but a new string is stored as is
+    /// instead of being copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`u32::MAX`] distinct strings.
+    #[inline]
+    pub fn intern_shared(&mut self, text: &SmolStr) -> (StringId, SmolStr) {
+        self.intern_with(text, || text.clone())
+    }
+
+    /// Get the id and stored copy of the passed string, adding the one created
+    /// by `to_owned` if it's new.
+    fn intern_with(
+        &mut self,
+        text: &str,
+        to_owned: impl FnOnce() -> SmolStr,
+    ) -> (StringId, SmolStr) {
+        if let Some((text, id)) = self.ids.get_key_value(text) {
+            return (*id, text.clone());
+        }
+
+        #[allow(clippy::expect_used)] // Unreachable for any real workspace.
+        let id = StringId(u32::try_from(self.strings.len()).expect("Too many strings."));
+        let text = to_owned();
+
+        self.ids.insert(text.clone(), id);
+        self.strings.push(text.clone());
+
+        (id, text)
+    }
+
+    /// Get the id of the passed string, if it was interned.
+    #[inline]
+    pub fn get(&self, text: &str) -> Option<StringId> {
+        self.ids.get(text).copied()
+    }
+
+    /// Get the string with the passed id.
+    #[inline]
+    pub fn resolve(&self, id: StringId) -> Option<&str> {
+        self.strings.get(id.index()).map(SmolStr::as_str)
+    }
+
+    /// The number of distinct strings.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Whether or not no strings were interned.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl From<Vec<SmolStr>> for Interner {
+    fn from(strings: Vec<SmolStr>) -> Self {
+        let mut interner = Self::new();
+        for string in &strings {
+            interner.intern_shared(string);
+        }
+
+        interner
+    }
+}
+
+impl From<Interner> for Vec<SmolStr> {
+    #[inline]
+    fn from(interner: Interner) -> Self {
+        interner.strings
+    }
+}
+
+// The ids are derived from the strings, so only the strings are compared.
+
+impl PartialEq for Interner {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.strings == other.strings
+    }
+}
+
+impl Eq for Interner {}
+
+impl Hash for Interner {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.strings.hash(state);
+    }
+}
+
+impl PartialOrd for Interner {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interner {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.strings.cmp(&other.strings)
+    }
+}
@@ -1,28 +1,55 @@
 //! The actual lexer.
 
+use lsp_types::Position;
 use smol_str::SmolStr;
-use std::ops::{Deref, DerefMut};
+use std::{
+    mem,
+    ops::{Deref, DerefMut},
+};
 
 use crate::{
+    checkpoint::Checkpoint,
+    config::LexerConfig,
+    dialect::Dialect,
     error::Error,
+    input::LexerInput,
+    interner::{Interner, StringId},
     state::State,
-    token::{Comment, Token, TokenType, Trivia},
-    utils::can_be_identifier,
+    token::{
+        Comment, CompoundOperator, Literal, LuauString, Operator, PartialKeyword, Symbol, Token,
+        TokenType, Trivia, TriviaType,
+    },
+    utils::{advance_position, can_be_identifier_byte},
+    version::{LuauFeature, LuauFeatures},
 };
 
-/// The main component of this crate, the lexer.
+/// The main component of this crate, the lexer. It can lex any [`LexerInput`],
+/// the default being an owned [`String`].
 #[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct Lexer {
-    /// The characters in the input
-    pub(crate) chars: Vec<char>,
+pub struct Lexer<I = String> {
+    /// The input being lexed.
+    pub(crate) input: I,
 
     /// The errors met during lexing. They are added when [`Lexer::next_token`] is
-    /// called and gets emptied before any new tokens are lexed.
+    /// called and are returned before any new tokens are lexed. Returned errors
+    /// are kept so [`Lexer::rewind`] can bring them back.
     pub(crate) errors: Vec<Error>,
 
+    /// The number of [`errors`](Lexer::errors) which were already returned.
+    pub(crate) errors_read: usize,
+
     /// The current state of the lexer.
     pub(crate) state: State,
+
+    /// The configuration of the lexer.
+    pub(crate) config: LexerConfig,
+
+    /// The interner the texts of identifiers and strings go through, if any.
+    pub(crate) interner: Option<Interner>,
+
+    /// The id of the text of the last token, see [`Lexer::string_id`].
+    pub(crate) string_id: Option<StringId>,
 }
 
 impl Lexer {
@@ -32,18 +59,197 @@ impl Lexer {
         Self::default().with_input(input)
     }
 
+    /// Lex the passed code, failing on its first error. See
+    /// [`Lexer::try_collect_tokens`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error in the code.
+    #[inline]
+    pub fn tokenize_strict(input: &str) -> Result<Vec<Token>, Error> {
+        Self::new(input).try_collect_tokens()
+    }
+
+    /// Set the lexer's input like [`Lexer::set_input`], but copy it into the
+    /// already allocated input and keep the capacity of all other buffers, to
+    /// lex many files without reallocating.
+    pub fn reset(&mut self, input: &str) {
+        self.input.clear();
+        self.input.push_str(input);
+        self.restart();
+    }
+
+    /// Lex all passed inputs with this lexer, calling `visit` with the index of
+    /// the input and each of its tokens, the last one being
+    /// [`TokenType::EndOfFile`]. The lexer's buffers, its [`Interner`] and the
+    /// visited token are reused across inputs, so lexing many files barely
+    /// allocates once they are warmed up.
+    pub fn lex_batch<'a>(
+        &mut self,
+        inputs: impl IntoIterator<Item = &'a str>,
+        mut visit: impl FnMut(usize, &Token),
+    ) {
+        let mut token = Token::empty(TokenType::EndOfFile);
+
+        for (index, input) in inputs.into_iter().enumerate() {
+            self.reset(input);
+
+            loop {
+                self.next_token_into(&mut token);
+                visit(index, &token);
+
+                if token == TokenType::EndOfFile {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl<I: LexerInput> Lexer<I> {
+    /// Create a new [`Lexer`] over the passed [`LexerInput`], without copying it.
+    #[inline]
+    pub fn from_input(input: I) -> Self {
+        Self::from_parts(input, LexerConfig::new())
+    }
+
+    /// Create a new [`Lexer`] with the passed input and [`LexerConfig`].
+    pub(crate) fn from_parts(input: I, config: LexerConfig) -> Self {
+        let mut lexer = Self {
+            input,
+            errors: Vec::new(),
+            errors_read: 0,
+            state: State::default(),
+            config,
+            interner: None,
+            string_id: None,
+        };
+        lexer.last_trivia = lexer.skip_trivia();
+
+        lexer
+    }
+
     /// Set the lexer's input. Meant to be chained.
     #[inline]
-    pub fn with_input(mut self, input: &str) -> Self {
+    #[must_use]
+    pub fn with_input(mut self, input: impl Into<I>) -> Self {
         self.set_input(input);
         self
     }
 
-    /// Set the lexer's input.
+    /// Set the lexer's input. This resets the lexer's [`State`].
+    #[inline]
+    pub fn set_input(&mut self, input: impl Into<I>) {
+        self.input = input.into();
+        self.restart();
+    }
+
+    /// Start lexing at the passed byte offset of the input, which is at the
+    /// passed position, ignoring everything before it. This lexes part of a
+    /// larger document, like Luau embedded in Markdown, with the positions and
+    /// offsets of all tokens and errors being the ones in the document. Meant
+    /// to be chained.
+    #[inline]
+    #[must_use]
+    pub fn with_start(mut self, offset: usize, position: Position) -> Self {
+        self.set_start(offset, position);
+        self
+    }
+
+    /// Start lexing at the passed byte offset of the input, which is at the
+    /// passed position. See [`Lexer::with_start`].
+    #[inline]
+    pub fn set_start(&mut self, offset: usize, position: Position) {
+        self.restart_at(offset, position);
+    }
+
+    /// Go back to the start of the input, keeping the capacity of the lexer's
+    /// buffers.
+    #[inline]
+    fn restart(&mut self) {
+        self.restart_at(0, Position::default());
+    }
+
+    /// Go back to the passed offset and position, keeping the capacity of the
+    /// lexer's buffers.
+    fn restart_at(&mut self, offset: usize, position: Position) {
+        let mut trivia = mem::take(&mut self.state.last_trivia);
+        trivia.clear();
+
+        self.errors.clear();
+        self.errors_read = 0;
+        self.string_id = None;
+        self.state = State {
+            position: offset,
+            lexer_position: position,
+            trivia_start: offset,
+            trivia_start_position: position,
+            ..State::default()
+        };
+        self.skip_trivia_into(&mut trivia);
+        self.last_trivia = trivia;
+    }
+
+    /// Get the lexer's input.
+    #[inline]
+    pub const fn input(&self) -> &I {
+        &self.input
+    }
+
+    /// Set the lexer's [`LexerConfig`]. This must be done before setting the
+    /// input as it affects how the leading trivia is lexed. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_config(mut self, config: LexerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the [`Interner`] the texts of identifiers and strings go through.
+    /// Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub fn with_interner(mut self, interner: Interner) -> Self {
+        self.interner = Some(interner);
+        self
+    }
+
+    /// Set the [`Interner`] the texts of identifiers and strings go through.
+    #[inline]
+    pub fn set_interner(&mut self, interner: Option<Interner>) {
+        self.interner = interner;
+    }
+
+    /// Get the lexer's [`Interner`], if any.
+    #[inline]
+    pub const fn interner(&self) -> Option<&Interner> {
+        self.interner.as_ref()
+    }
+
+    /// Take the lexer's [`Interner`] out, to reuse it in another lexer.
     #[inline]
-    pub fn set_input(&mut self, input: &str) {
-        self.chars = input.chars().collect();
-        self.last_trivia = self.skip_trivia();
+    pub const fn take_interner(&mut self) -> Option<Interner> {
+        self.interner.take()
+    }
+
+    /// Get the [`StringId`] of the text of the last token returned by
+    /// [`Lexer::next_token`], if it's an identifier or a string and the lexer
+    /// has an [`Interner`].
+    #[inline]
+    pub const fn string_id(&self) -> Option<StringId> {
+        self.string_id
+    }
+
+    /// Get the lexer's [`LexerConfig`].
+    #[inline]
+    pub const fn config(&self) -> &LexerConfig {
+        &self.config
+    }
+
+    /// Get the lexer's [`Dialect`].
+    #[inline]
+    pub const fn dialect(&self) -> Dialect {
+        self.config.dialect
     }
 
     /// Save the current [`State`]. To be used with [`Lexer::set_state`].
@@ -58,44 +264,371 @@ impl Lexer {
         self.state = state;
     }
 
+    /// Create a [`Checkpoint`] at the current position, to go back to with
+    /// [`Lexer::rewind`]. This is much cheaper than [`Lexer::save_state`].
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: self.position,
+            lexer_position: self.lexer_position,
+            trivia_start: self.trivia_start,
+            trivia_start_position: self.trivia_start_position,
+            errors_count: self.errors_count,
+            errors_len: self.errors.len(),
+            errors_read: self.errors_read,
+        }
+    }
+
+    /// Go back to the passed [`Checkpoint`], which must have been created by
+    /// this lexer with the same input.
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        // The trivia only depends on where it starts, so it's only lexed again
+        // if the lexer moved.
+        if (self.position, self.trivia_start) != (checkpoint.position, checkpoint.trivia_start) {
+            self.state.position = checkpoint.trivia_start;
+            self.state.lexer_position = checkpoint.trivia_start_position;
+            self.last_trivia = self.skip_trivia();
+        }
+
+        self.state.position = checkpoint.position;
+        self.state.lexer_position = checkpoint.lexer_position;
+        self.trivia_start = checkpoint.trivia_start;
+        self.trivia_start_position = checkpoint.trivia_start_position;
+        self.errors_count = checkpoint.errors_count;
+        self.errors.truncate(checkpoint.errors_len);
+        self.errors_read = checkpoint.errors_read;
+    }
+
+    /// Run the passed function and [`rewind`](Lexer::rewind) to before it if it
+    /// fails, like when trying to lex a possible generic type.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the passed function, if any.
+    pub fn try_speculate<T, E>(
+        &mut self,
+        function: impl FnOnce(&mut Self) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let checkpoint = self.checkpoint();
+        let result = function(self);
+
+        if result.is_err() {
+            self.rewind(checkpoint);
+        }
+
+        result
+    }
+
     /// Lex the next token. This will return any errors met while parsing the
-    /// *previous* token before lexing a new one.
+    /// *previous* token before lexing a new one. Such errors are zero-width
+    /// tokens placed at the current position, use [`Error::start`] and
+    /// [`Error::end`] to get where they actually are.
     pub fn next_token(&mut self) -> Token {
-        if !self.errors.is_empty() {
-            let error = self.errors.remove(0);
-            let start = error.start();
+        let mut token = Token::empty(TokenType::EndOfFile);
+        self.next_token_into(&mut token);
 
-            return TokenType::Error(error).into_token(
-                start,
-                self.lexer_position,
-                Vec::new(),
-                Vec::new(),
-            );
+        token
+    }
+
+    /// Same as [`Lexer::next_token`], but lex the token into the passed one,
+    /// reusing the capacity of its trivia.
+    pub fn next_token_into(&mut self, token: &mut Token) {
+        token.leading_trivia.clear();
+        token.trailing_trivia.clear();
+        self.string_id = None;
+
+        // Queued errors come after the trivia of their token, so spaces and
+        // comments lexed as tokens come first to keep both modes equivalent.
+        if self.errors_read < self.errors.len() && !self.is_at_trivia_token() {
+            let error = self.errors[self.errors_read].clone();
+            self.errors_read += 1;
+
+            // These errors belong to an already lexed token, so they don't own
+            // any text. The actual location can be found in the error itself.
+            token.start = self.lexer_position;
+            token.end = self.lexer_position;
+            token.token_type = TokenType::Error(error);
+
+            self.count_error(token);
+            return;
         }
 
         let start = self.lexer_position;
 
         let token_type = TokenType::try_lex(self).unwrap_or(TokenType::EndOfFile);
+        let end = self.lexer_position;
+
+        if self.config.dialect.is_luau() && self.config.luau_features != LuauFeatures::ALL {
+            self.check_features(&token_type, start, end);
+        }
 
-        let trivia = self.skip_trivia();
-        let leading_trivia = self.last_trivia.clone();
-        let trailing_trivia = trivia.clone();
+        self.trivia_start = self.position;
+        self.trivia_start_position = self.lexer_position;
 
-        self.last_trivia = trivia;
+        self.skip_trivia_into(&mut token.trailing_trivia);
+
+        // Fresh tokens take the buffer of the last trivia instead of cloning
+        // it, while reused ones keep their own buffers so they stop allocating.
+        if token.leading_trivia.capacity() < self.last_trivia.len() {
+            mem::swap(&mut token.leading_trivia, &mut self.state.last_trivia);
+        } else {
+            token.leading_trivia.extend_from_slice(&self.last_trivia);
+            self.last_trivia.clear();
+        }
+        self.last_trivia.extend_from_slice(&token.trailing_trivia);
 
-        token_type.into_token(start, self.lexer_position, leading_trivia, trailing_trivia)
+        token.start = start;
+        token.end = end;
+        token.token_type = token_type;
+
+        if matches!(token.token_type, TokenType::Error(_)) {
+            self.count_error(token);
+        }
+    }
+
+    /// Whether or not the next token is spaces or a comment, which would be
+    /// trivia if they weren't lexed as tokens.
+    fn is_at_trivia_token(&self) -> bool {
+        match self.current_char() {
+            Some('-') => self.config.comments_as_tokens && self.next_char() == Some('-'),
+            Some(character) => {
+                self.config.spaces_as_tokens && self.config.whitespace.is_whitespace(character)
+            }
+            None => false,
+        }
+    }
+
+    /// Count the passed error token towards [`LexerConfig::max_errors`], stopping
+    /// the lexer and replacing it with an error saying so if there are too many
+    /// errors.
+    fn count_error(&mut self, token: &mut Token) {
+        let TokenType::Error(error) = &token.token_type else {
+            return;
+        };
+        if error.is_warning() {
+            return;
+        }
+
+        self.errors_count += 1;
+
+        if let Some(max_errors) = self.config.max_errors
+            && self.errors_count > max_errors
+        {
+            self.errors_read = self.errors.len();
+            self.last_trivia.clear();
+            self.advance_to(self.input.len());
+            self.trivia_start = self.state.position;
+            self.trivia_start_position = self.lexer_position;
+
+            token.token_type = TokenType::Error(Error::new(
+                token.start,
+                format!("Stopped lexing after reaching the maximum of {max_errors} errors."),
+                None,
+            ));
+            token.end = token.start;
+            token.leading_trivia.clear();
+            token.trailing_trivia.clear();
+        }
+    }
+
+    /// Report the passed token if it uses features the targeted runtime
+    /// doesn't support.
+    fn check_features(&mut self, token_type: &TokenType, start: Position, end: Position) {
+        let (is_compound_assignment, is_floor_division) = match token_type {
+            TokenType::CompoundOperator(CompoundOperator::FloorDivisionEqual) => (true, true),
+            TokenType::CompoundOperator(
+                CompoundOperator::EqualEqual
+                | CompoundOperator::LessThanOrEqualTo
+                | CompoundOperator::GreaterThanOrEqualTo,
+            ) => (false, false),
+            TokenType::CompoundOperator(_) => (true, false),
+            TokenType::Operator(Operator::FloorDivision) => (false, true),
+            _ => (false, false),
+        };
+
+        let used_features = [
+            (is_compound_assignment, LuauFeature::CompoundAssignment),
+            (is_floor_division, LuauFeature::FloorDivision),
+            (
+                matches!(
+                    token_type,
+                    TokenType::Literal(Literal::String(LuauString::Backticks(_)))
+                ),
+                LuauFeature::StringInterpolation,
+            ),
+            (
+                *token_type == TokenType::Symbol(Symbol::At),
+                LuauFeature::Attributes,
+            ),
+            (
+                *token_type == TokenType::PartialKeyword(PartialKeyword::Type)
+                    && !self
+                        .config
+                        .luau_features
+                        .contains(LuauFeature::TypeFunctions)
+                    && self.is_next_word("function"),
+                LuauFeature::TypeFunctions,
+            ),
+        ];
+
+        for (is_used, feature) in used_features {
+            if is_used && !self.config.luau_features.contains(feature) {
+                self.errors.push(
+                    Error::new(
+                        start,
+                        format!("The targeted luau version doesn't support {feature}."),
+                        Some(end),
+                    )
+                    .with_severity(self.config.unsupported_syntax),
+                );
+            }
+        }
+    }
+
+    /// Whether or not the next word, after any trivia, is the passed one. This
+    /// doesn't move the lexer.
+    fn is_next_word(&mut self, word: &str) -> bool {
+        let state = self.save_state();
+        let errors_count = self.errors.len();
+
+        // Spaces and comments are skipped even if they're lexed as tokens.
+        self.lex_trivia_into(&mut Vec::new(), true, true);
+        let is_next_word = self
+            .current_char()
+            .is_some_and(|character| self.config.identifiers.is_identifier_start(character))
+            && {
+                let start = self.position;
+                self.skip_identifier();
+
+                self.input.slice(start..self.position) == word
+            };
+
+        self.errors.truncate(errors_count);
+        self.set_state(state);
+
+        is_next_word
+    }
+
+    /// Lex all remaining tokens, the last one being [`TokenType::EndOfFile`].
+    pub fn collect_tokens(&mut self) -> Vec<Token> {
+        let mut tokens = Vec::new();
+
+        loop {
+            let token = self.next_token();
+            let is_done = token == TokenType::EndOfFile;
+
+            tokens.push(token);
+            if is_done {
+                break tokens;
+            }
+        }
+    }
+
+    /// Lex all remaining tokens like [`Lexer::collect_tokens`], along with the
+    /// [`StringId`] of each of them, see [`Lexer::string_id`].
+    pub fn collect_tokens_with_ids(&mut self) -> (Vec<Token>, Vec<Option<StringId>>) {
+        let mut tokens = Vec::new();
+        let mut ids = Vec::new();
+
+        loop {
+            let token = self.next_token();
+            let is_done = token == TokenType::EndOfFile;
+
+            tokens.push(token);
+            ids.push(self.string_id);
+            if is_done {
+                break (tokens, ids);
+            }
+        }
+    }
+
+    /// Lex all remaining tokens like [`Lexer::collect_tokens`], but stop at the
+    /// first error instead of recovering from it. If [`LexerConfig::max_errors`]
+    /// is set, that many errors are kept as tokens and lexing only stops once
+    /// there are more. Warnings never stop lexing.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error, or the one saying the maximum was reached.
+    pub fn try_collect_tokens(&mut self) -> Result<Vec<Token>, Error> {
+        let max_errors = self.config.max_errors.unwrap_or(0);
+        let mut tokens = Vec::new();
+
+        loop {
+            let token = self.next_token();
+
+            match token.token_type {
+                TokenType::Error(error)
+                    if !error.is_warning() && self.errors_count > max_errors =>
+                {
+                    break Err(error);
+                }
+                TokenType::EndOfFile => {
+                    tokens.push(token);
+                    break Ok(tokens);
+                }
+                _ => tokens.push(token),
+            }
+        }
+    }
+
+    /// Move the lexer by the passed character. This is the same as
+    /// [`State::increment_position_by_char`] but respects [`LexerConfig::tab_width`].
+    #[inline]
+    pub const fn increment_position_by_char(&mut self, character: char) {
+        self.state.increment_position_by_char(character);
+
+        if character == '\t' {
+            self.state.lexer_position.character += self.config.tab_width - 1;
+        }
     }
 
     /// Get the current character.
     #[inline]
     pub fn current_char(&self) -> Option<char> {
-        self.chars.get(self.position).copied()
+        match self.input.byte_at(self.position)? {
+            byte if byte.is_ascii() => Some(byte as char),
+            _ => self.input.char_at(self.position),
+        }
     }
 
     /// Get the next character.
     #[inline]
     pub fn next_char(&self) -> Option<char> {
-        self.chars.get(self.position + 1).copied()
+        let current_char = self.current_char()?;
+
+        self.input.char_at(self.position + current_char.len_utf8())
+    }
+
+    /// Move the lexer to the passed byte offset, which must be at a character
+    /// boundary after the current position.
+    pub(crate) fn advance_to(&mut self, offset: usize) {
+        let text = self.input.slice(self.state.position..offset);
+
+        self.state.lexer_position =
+            advance_position(self.state.lexer_position, &text, self.config.tab_width);
+        self.state.position = offset;
+    }
+
+    /// Get the input from the passed byte offset up to the current position.
+    #[inline]
+    pub(crate) fn slice_from(&self, start: usize) -> SmolStr {
+        SmolStr::new(self.input.slice(start..self.position))
+    }
+
+    /// Same as [`Lexer::slice_from`], but through the interner if there's one.
+    /// The text is looked up straight from the input, so it's only copied the
+    /// first time it's met.
+    pub(crate) fn intern_from(&mut self, start: usize) -> SmolStr {
+        let text = self.input.slice(start..self.position);
+        let Some(interner) = &mut self.interner else {
+            return SmolStr::new(text);
+        };
+
+        let (id, text) = interner.intern_str(&text);
+        self.string_id = Some(id);
+
+        text
     }
 
     /// Move the lexer after the current character if it matches the passed one,
@@ -134,37 +667,89 @@ impl Lexer {
     /// one character to form a valid identifier at the current position,
     pub fn consume_identifier(&mut self) -> SmolStr {
         let start = self.position;
-        while let Some(character) = self.current_char() {
-            if can_be_identifier(character) {
-                self.increment_position_by_char(character);
+        self.skip_identifier();
+
+        self.slice_from(start)
+    }
+
+    /// Move the lexer after the next identifier, see [`Lexer::consume_identifier`].
+    pub(crate) fn skip_identifier(&mut self) {
+        let start = self.position;
+        let mut end = start;
+        let mut is_ascii = true;
+
+        while let Some(byte) = self.input.byte_at(end) {
+            if can_be_identifier_byte(byte) {
+                end += 1;
+            } else if let Some(character) = self.input.char_at(end)
+                && !character.is_ascii()
+                && self.config.identifiers.can_be_identifier(character)
+            {
+                end += character.len_utf8();
+                is_ascii = false;
             } else {
                 break;
             }
         }
 
-        SmolStr::from_iter(self.chars[start..self.position].to_vec())
+        if is_ascii {
+            // Ascii identifiers are one column per byte and have no new lines.
+            self.increment_position((end - start) as u32);
+        } else {
+            self.advance_to(end);
+        }
     }
 
     /// Get the trivia after the current position and move the lexer to after them.
     #[allow(clippy::missing_panics_doc)] // SAFETY: Will never actually panic.
     pub fn skip_trivia(&mut self) -> Vec<Trivia> {
         let mut trivia = Vec::new();
+        self.skip_trivia_into(&mut trivia);
+
+        trivia
+    }
 
+    /// Same as [`Lexer::skip_trivia`], but push the trivia to the passed vector.
+    #[inline]
+    pub fn skip_trivia_into(&mut self, trivia: &mut Vec<Trivia>) {
+        self.lex_trivia_into(
+            trivia,
+            !self.config.spaces_as_tokens,
+            !self.config.comments_as_tokens,
+        );
+    }
+
+    /// Push the trivia after the current position to the passed vector, only
+    /// including spaces and comments if asked to, and move the lexer to after
+    /// them.
+    #[allow(clippy::missing_panics_doc)] // SAFETY: Will never actually panic.
+    fn lex_trivia_into(&mut self, trivia: &mut Vec<Trivia>, spaces: bool, comments: bool) {
         loop {
-            let spaces = self.skip_whitespace();
+            let start = self.lexer_position;
+            let start_offset = self.position;
+            let skipped = if spaces {
+                self.skip_whitespace()
+            } else {
+                SmolStr::default()
+            };
 
-            if !spaces.is_empty() {
-                trivia.push(Trivia::Spaces(spaces));
-            } else if self.current_char() == Some('-') && self.consume_with_next('-') {
+            let trivia_type = if !skipped.is_empty() {
+                TriviaType::Spaces(skipped)
+            } else if comments && self.current_char() == Some('-') && self.consume_with_next('-') {
                 // SAFETY: Will always return `Some(_)`. It's just the trait definition.
                 #[allow(clippy::unwrap_used)]
-                trivia.push(Trivia::Comment(Comment::try_lex(self).unwrap()));
+                TriviaType::Comment(Comment::try_lex(self).unwrap())
             } else {
                 break;
-            }
-        }
+            };
 
-        trivia
+            trivia.push(trivia_type.into_trivia(
+                start,
+                self.lexer_position,
+                start_offset,
+                self.position,
+            ));
+        }
     }
 
     /// Get the whitespaces after the current positive and move the lexer to after
@@ -172,20 +757,22 @@ impl Lexer {
     pub fn skip_whitespace(&mut self) -> SmolStr {
         let start = self.position;
         while let Some(character) = self.current_char() {
-            if character.is_whitespace() {
+            if self.config.whitespace.is_whitespace(character) {
                 self.increment_position_by_char(character);
             } else {
                 break;
             }
         }
 
-        (start != self.position)
-            .then(|| SmolStr::from_iter(self.chars[start..self.position].to_vec()))
-            .unwrap_or_default()
+        if start == self.position {
+            SmolStr::default()
+        } else {
+            self.slice_from(start)
+        }
     }
 }
 
-impl Deref for Lexer {
+impl<I> Deref for Lexer<I> {
     type Target = State;
 
     fn deref(&self) -> &Self::Target {
@@ -193,7 +780,7 @@ impl Deref for Lexer {
     }
 }
 
-impl DerefMut for Lexer {
+impl<I> DerefMut for Lexer<I> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.state
     }
@@ -202,5 +789,5 @@ impl DerefMut for Lexer {
 /// A trait which means this item can be lexed.
 pub trait Lexable: Sized {
     /// Try lexing the item.
-    fn try_lex(lexer: &mut Lexer) -> Option<Self>;
+    fn try_lex<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self>;
 }
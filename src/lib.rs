@@ -22,12 +22,13 @@
 
 /// A simple macro to reexport modules and include them in [`prelude`].
 macro_rules! reexport {
-    ($($name: ident $({$($inner:ident as $inner_name:ident),+ $(,)?})?),* $(,)?) => {
-        $( pub mod $name; )*
+    ($($(#[$meta: meta])* $name: ident $({$($inner:ident as $inner_name:ident),+ $(,)?})?),* $(,)?) => {
+        $( $(#[$meta])* pub mod $name; )*
 
         /// Loads all needed items for outside crates to use.
         pub mod prelude {
             $(
+                $(#[$meta])*
                 pub use crate::$name::*;
                 $($( pub use crate::$name::$inner as $inner_name; )+)?
             )*
@@ -70,10 +71,33 @@ macro_rules! impl_from {
 
 mod utils;
 
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+pub mod ffi;
+
 reexport!(
     lexer,
+    checkpoint,
+    cursor,
+    input,
+    interner,
+    config,
     state,
+    dialect,
+    version,
     position { Ext as PositionExt },
     error,
-    token
+    token,
+    tree,
+    folding,
+    store,
+    context,
+    index,
+    render,
+    minifier,
+    cache,
+    workspace,
+    markdown,
+    #[cfg(feature = "serde")]
+    schema,
 );
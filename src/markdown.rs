@@ -0,0 +1,144 @@
+//! Finding and lexing the Lua and Luau code blocks of Markdown documents.
+
+use lsp_types::Position;
+
+use crate::prelude::{Lexer, LexerConfig, Token};
+
+/// A fenced block of Lua or Luau code in a Markdown document.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CodeFence<'a> {
+    /// The language of the block, `lua` or `luau` in any case.
+    pub language: &'a str,
+
+    /// The code in the block, without the fences.
+    pub code: &'a str,
+
+    /// The byte offset of the code in the document.
+    pub offset: usize,
+
+    /// The position of the code in the document.
+    pub position: Position,
+
+    /// The document up to the end of the code, which is what's actually lexed
+    /// so offsets are the ones in the document.
+    document: &'a str,
+}
+
+impl<'a> CodeFence<'a> {
+    /// Create a [`Lexer`] over the code, with all positions and offsets being
+    /// the ones in the document.
+    #[inline]
+    pub fn lexer(&self, config: LexerConfig) -> Lexer<&'a str> {
+        config
+            .build_from(self.document)
+            .with_start(self.offset, self.position)
+    }
+}
+
+/// An opened fence whose closing one wasn't found yet.
+struct OpenFence<'a> {
+    /// The character the fence is made of, a backtick or a tilde.
+    character: char,
+
+    /// How many times the character is repeated.
+    length: usize,
+
+    /// The first word of the info string.
+    language: &'a str,
+
+    /// The byte offset of the line after the fence.
+    offset: usize,
+
+    /// The line after the fence.
+    line: u32,
+}
+
+impl<'a> OpenFence<'a> {
+    /// Close the fence at the passed offset, getting its code if it's Lua or
+    /// Luau.
+    fn close(self, markdown: &'a str, end: usize) -> Option<CodeFence<'a>> {
+        let is_lua = ["lua", "luau"]
+            .iter()
+            .any(|language| self.language.eq_ignore_ascii_case(language));
+
+        is_lua.then(|| CodeFence {
+            language: self.language,
+            code: &markdown[self.offset..end],
+            offset: self.offset,
+            position: Position::new(self.line, 0),
+            document: &markdown[..end],
+        })
+    }
+}
+
+/// Get the character, length, and info string of the fence on the passed line,
+/// if it is one.
+fn parse_fence(line: &str) -> Option<(char, usize, &str)> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let indentation = line.len() - line.trim_start_matches(' ').len();
+    if indentation > 3 {
+        return None;
+    }
+
+    let line = &line[indentation..];
+    let character = line.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let length = line.len() - line.trim_start_matches(character).len();
+    let info = line[length..].trim();
+
+    if length < 3 || (character == '`' && info.contains('`')) {
+        None
+    } else {
+        Some((character, length, info))
+    }
+}
+
+/// Find all ```` ```lua ```` and ```` ```luau ```` fenced code blocks in the
+/// passed Markdown document. Blocks which are never closed run until the end
+/// of the document.
+pub fn code_fences(markdown: &str) -> Vec<CodeFence<'_>> {
+    let mut fences = Vec::new();
+    let mut open: Option<OpenFence> = None;
+    let mut offset = 0;
+
+    for (line_number, line) in markdown.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += line.len();
+
+        let Some((character, length, info)) = parse_fence(line) else {
+            continue;
+        };
+
+        match open.take() {
+            None => {
+                open = Some(OpenFence {
+                    character,
+                    length,
+                    language: info.split_whitespace().next().unwrap_or_default(),
+                    offset,
+                    line: line_number as u32 + 1,
+                });
+            }
+            Some(fence)
+                if character == fence.character && length >= fence.length && info.is_empty() =>
+            {
+                fences.extend(fence.close(markdown, line_start));
+            }
+            fence => open = fence,
+        }
+    }
+
+    if let Some(fence) = open {
+        fences.extend(fence.close(markdown, markdown.len()));
+    }
+
+    fences
+}
+
+/// Lex all ```` ```lua ```` and ```` ```luau ```` fenced code blocks in the
+/// passed Markdown document, see [`code_fences`].
+pub fn lex_code_fences(markdown: &str, config: LexerConfig) -> Vec<(CodeFence<'_>, Vec<Token>)> {
+    code_fences(markdown)
+        .into_iter()
+        .map(|fence| (fence, fence.lexer(config).collect_tokens()))
+        .collect()
+}
@@ -0,0 +1,167 @@
+//! The [`Minifier`].
+
+use crate::{
+    prelude::{Comment, Error, Lexer, Token, TokenKind, TokenType, TriviaType},
+    utils::can_be_identifier,
+};
+
+/// Shrinks luau code by dropping comments and all whitespace that isn't needed
+/// to keep adjacent tokens apart.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Minifier {
+    /// Whether or not to keep `--!` directives at the start of the file.
+    keep_directives: bool,
+
+    /// Whether or not to keep the comments at the start of the file.
+    keep_header: bool,
+}
+
+impl Minifier {
+    /// Create a new [`Minifier`] which keeps directives but drops the header.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            keep_directives: true,
+            keep_header: false,
+        }
+    }
+
+    /// Set whether or not to keep `--!` directives (like `--!strict`) found
+    /// before the first token. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn keep_directives(mut self, keep_directives: bool) -> Self {
+        self.keep_directives = keep_directives;
+        self
+    }
+
+    /// Set whether or not to keep all comments found before the first token,
+    /// usually a license header. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn keep_header(mut self, keep_header: bool) -> Self {
+        self.keep_header = keep_header;
+        self
+    }
+
+    /// Whether or not the passed comment should be kept.
+    fn should_keep(&self, comment: &Comment) -> bool {
+        self.keep_header
+            || self.keep_directives
+                && matches!(comment, Comment::SingleLine(comment) if comment.starts_with("--!"))
+    }
+
+    /// Minify the passed code.
+    ///
+    /// # Errors
+    ///
+    /// Returns all lexing errors, excluding warnings, if the code isn't valid,
+    /// as the output would most likely be wrong. Also returns an error at the
+    /// first token which the output would be lexed differently at, which
+    /// would be a bug in the minifier.
+    pub fn minify(&self, code: &str) -> Result<String, Vec<Error>> {
+        let tokens = Lexer::new(code).collect_tokens();
+        let errors = tokens
+            .iter()
+            .filter_map(|token| match &token.token_type {
+                TokenType::Error(error) if !error.is_warning() => Some(error.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut output = String::with_capacity(code.len() / 2);
+        if let Some(first) = tokens.first() {
+            for trivia in &first.leading_trivia {
+                if let TriviaType::Comment(comment) = &trivia.trivia_type
+                    && self.should_keep(comment)
+                {
+                    output.push_str(comment.as_str());
+                    output.push('\n');
+                }
+            }
+        }
+
+        let mut previous: Option<(&Token, String)> = None;
+        for token in &tokens {
+            let Some(text) = token.token_type.try_as_string() else {
+                continue;
+            };
+
+            if let Some((previous, previous_text)) = &previous
+                && needs_separator((previous, previous_text), (token, &text))
+            {
+                output.push(' ');
+            }
+
+            output.push_str(&text);
+            previous = Some((token, text));
+        }
+
+        // Checking pairs of tokens can't catch joins spanning more tokens, so
+        // the whole output is checked once.
+        let minified = Lexer::new(&output).collect_tokens();
+        let mismatch = significant_tokens(&tokens)
+            .zip(significant_tokens(&minified))
+            .find(|(token, minified)| token.token_type != minified.token_type);
+
+        if let Some((token, _)) = mismatch {
+            return Err(vec![Error::new(
+                token.start,
+                "The minified code would be lexed differently from here.",
+                Some(token.end),
+            )]);
+        }
+
+        Ok(output)
+    }
+}
+
+/// Get the tokens which must be the same in the code and its minified version,
+/// which is all of them except warnings. Trivia are already left out.
+fn significant_tokens(tokens: &[Token]) -> impl Iterator<Item = &Token> {
+    tokens
+        .iter()
+        .filter(|token| !matches!(&token.token_type, TokenType::Error(error) if error.is_warning()))
+}
+
+impl Default for Minifier {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether or not the two tokens would be lexed differently if written with
+/// nothing between them, like `-` and `-` becoming a comment or `a` and `b`
+/// becoming `ab`. Only pairs of symbols, which may merge into a longer one, are
+/// actually lexed again.
+fn needs_separator(
+    (previous, previous_text): (&Token, &str),
+    (next, next_text): (&Token, &str),
+) -> bool {
+    let (Some(last), Some(first)) = (previous_text.chars().next_back(), next_text.chars().next())
+    else {
+        return false;
+    };
+
+    // Luau reads numbers greedily, so `1local` and `1..x` are malformed numbers
+    // even if this lexer reads them as two tokens. Better be safe.
+    if can_be_identifier(last) && can_be_identifier(first)
+        || previous.token_type.kind() == TokenKind::Number && first == '.'
+    {
+        return true;
+    }
+    if !last.is_ascii_punctuation() || !first.is_ascii_punctuation() {
+        return false;
+    }
+
+    let mut lexer = Lexer::new(&format!("{previous_text}{next_text}"));
+
+    lexer.next_token() != previous.token_type
+        || lexer.next_token() != next.token_type
+        || lexer.next_token() != TokenType::EndOfFile
+}
@@ -0,0 +1,200 @@
+//! The [`AnsiRenderer`].
+
+use std::fmt::Write;
+
+use crate::{
+    prelude::{Token, TokenKind},
+    render::{Theme, segments},
+};
+
+/// A terminal color.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub enum AnsiColor {
+    /// A color from the 256-color palette.
+    Palette(u8),
+
+    /// A 24-bit color. Needs a terminal with truecolor support.
+    Rgb(u8, u8, u8),
+}
+
+/// How a [`TokenKind`] is displayed in the terminal.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct AnsiStyle {
+    /// The foreground color.
+    pub foreground: Option<AnsiColor>,
+
+    /// Whether or not the text is bold.
+    pub bold: bool,
+
+    /// Whether or not the text is italic.
+    pub italic: bool,
+
+    /// Whether or not the text is underlined.
+    pub underline: bool,
+}
+
+impl AnsiStyle {
+    /// Create a new [`AnsiStyle`] with the passed foreground color.
+    #[inline]
+    pub const fn new(foreground: AnsiColor) -> Self {
+        Self {
+            foreground: Some(foreground),
+            bold: false,
+            italic: false,
+            underline: false,
+        }
+    }
+
+    /// Make the text bold. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Make the text italic. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Make the text underlined. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Write the escape sequence enabling this style, returning whether or not
+    /// anything was written.
+    fn write_prefix(&self, output: &mut String) -> bool {
+        let mut codes = String::new();
+        let flags = [(self.bold, "1"), (self.italic, "3"), (self.underline, "4")];
+
+        for (_, code) in flags.into_iter().filter(|(enabled, _)| *enabled) {
+            codes.push_str(code);
+            codes.push(';');
+        }
+
+        // Writing to a string never fails.
+        let _ = match self.foreground {
+            Some(AnsiColor::Palette(color)) => write!(codes, "38;5;{color}"),
+            Some(AnsiColor::Rgb(red, green, blue)) => write!(codes, "38;2;{red};{green};{blue}"),
+            None => Ok(()),
+        };
+
+        let codes = codes.trim_end_matches(';');
+        if codes.is_empty() {
+            return false;
+        }
+
+        output.push_str("\x1b[");
+        output.push_str(codes);
+        output.push('m');
+
+        true
+    }
+}
+
+/// Renders lexed code with ANSI escape sequences, for use in terminals. Trivia
+/// is kept as is, only comments get styled.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct AnsiRenderer {
+    /// The style of each kind.
+    theme: Theme<AnsiStyle>,
+}
+
+impl AnsiRenderer {
+    /// Create a new [`AnsiRenderer`] with the passed theme.
+    #[inline]
+    pub const fn new(theme: Theme<AnsiStyle>) -> Self {
+        Self { theme }
+    }
+
+    /// A theme using the 256-color palette, works in most terminals.
+    pub const fn palette_theme() -> Theme<AnsiStyle> {
+        Self::theme_from(
+            AnsiColor::Palette(203),
+            AnsiColor::Palette(180),
+            AnsiColor::Palette(114),
+            AnsiColor::Palette(244),
+            AnsiColor::Palette(176),
+            AnsiColor::Palette(75),
+        )
+    }
+
+    /// A theme using 24-bit colors, needs a terminal with truecolor support.
+    pub const fn truecolor_theme() -> Theme<AnsiStyle> {
+        Self::theme_from(
+            AnsiColor::Rgb(224, 108, 117),
+            AnsiColor::Rgb(209, 154, 102),
+            AnsiColor::Rgb(152, 195, 121),
+            AnsiColor::Rgb(127, 132, 142),
+            AnsiColor::Rgb(198, 120, 221),
+            AnsiColor::Rgb(97, 175, 239),
+        )
+    }
+
+    /// Create a theme from the passed colors.
+    const fn theme_from(
+        error: AnsiColor,
+        number: AnsiColor,
+        string: AnsiColor,
+        comment: AnsiColor,
+        keyword: AnsiColor,
+        operator: AnsiColor,
+    ) -> Theme<AnsiStyle> {
+        let mut styles = [None; TokenKind::ALL.len()];
+
+        styles[TokenKind::Error as usize] = Some(AnsiStyle::new(error).underline());
+        styles[TokenKind::Number as usize] = Some(AnsiStyle::new(number));
+        styles[TokenKind::String as usize] = Some(AnsiStyle::new(string));
+        styles[TokenKind::Boolean as usize] = Some(AnsiStyle::new(number));
+        styles[TokenKind::Comment as usize] = Some(AnsiStyle::new(comment).italic());
+        styles[TokenKind::Keyword as usize] = Some(AnsiStyle::new(keyword).bold());
+        styles[TokenKind::PartialKeyword as usize] = Some(AnsiStyle::new(keyword));
+        styles[TokenKind::Operator as usize] = Some(AnsiStyle::new(operator));
+        styles[TokenKind::CompoundOperator as usize] = Some(AnsiStyle::new(operator));
+
+        Theme { styles }
+    }
+
+    /// Get the theme used by this renderer.
+    #[inline]
+    pub const fn theme(&self) -> &Theme<AnsiStyle> {
+        &self.theme
+    }
+
+    /// Render the passed source code, which must be the code the tokens were
+    /// lexed from.
+    pub fn render(&self, source: &str, tokens: &[Token]) -> String {
+        let mut output = String::with_capacity(source.len() * 2);
+
+        for (kind, text) in segments(source, tokens) {
+            let is_styled = kind
+                .and_then(|kind| self.theme.get(kind))
+                .is_some_and(|style| style.write_prefix(&mut output));
+
+            output.push_str(text);
+            if is_styled {
+                output.push_str("\x1b[0m");
+            }
+        }
+
+        output
+    }
+}
+
+impl Default for AnsiRenderer {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::palette_theme())
+    }
+}
@@ -0,0 +1,128 @@
+//! The [`HtmlRenderer`].
+
+use smol_str::SmolStr;
+
+use crate::{
+    prelude::{Token, TokenKind},
+    render::{Theme, segments},
+};
+
+/// Which characters get escaped in the rendered HTML.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub enum HtmlEscape {
+    /// Only escape `&`, `<`, and `>`. Enough for element content.
+    Minimal,
+
+    /// Escape `&`, `<`, `>`, `"`, and `'`. Safe to use inside attributes too.
+    #[default]
+    Full,
+}
+
+impl HtmlEscape {
+    /// Write the passed text to the output, escaping it.
+    fn write(self, output: &mut String, text: &str) {
+        for character in text.chars() {
+            match character {
+                '&' => output.push_str("&amp;"),
+                '<' => output.push_str("&lt;"),
+                '>' => output.push_str("&gt;"),
+                '"' if self == Self::Full => output.push_str("&quot;"),
+                '\'' if self == Self::Full => output.push_str("&#39;"),
+                _ => output.push(character),
+            }
+        }
+    }
+}
+
+/// Renders lexed code into HTML.
+///
+/// Each token is wrapped in a `<span>` with the class name its [`TokenKind`]
+/// has in the [`theme`](Theme). Spaces, and kinds with no class name, are
+/// written as plain (escaped) text.
+///
+/// The output isn't wrapped in any element, put it in a `<pre>` to keep the
+/// spaces intact.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct HtmlRenderer {
+    /// The class names of each kind.
+    theme: Theme<SmolStr>,
+
+    /// How to escape text.
+    escape: HtmlEscape,
+}
+
+impl HtmlRenderer {
+    /// Create a new [`HtmlRenderer`] with the [default theme](HtmlRenderer::default_theme).
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_theme(Self::default_theme())
+    }
+
+    /// Create a new [`HtmlRenderer`] with the passed class names.
+    #[inline]
+    pub const fn with_theme(theme: Theme<SmolStr>) -> Self {
+        Self {
+            theme,
+            escape: HtmlEscape::Full,
+        }
+    }
+
+    /// Set how text is escaped. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_escape(mut self, escape: HtmlEscape) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// The default class names. Identifiers and the end of file aren't styled.
+    pub fn default_theme() -> Theme<SmolStr> {
+        Theme::new()
+            .with(TokenKind::Error, "err".into())
+            .with(TokenKind::Number, "num".into())
+            .with(TokenKind::String, "str".into())
+            .with(TokenKind::Boolean, "bool".into())
+            .with(TokenKind::Comment, "com".into())
+            .with(TokenKind::Keyword, "kw".into())
+            .with(TokenKind::PartialKeyword, "kw".into())
+            .with(TokenKind::Symbol, "sym".into())
+            .with(TokenKind::Operator, "op".into())
+            .with(TokenKind::CompoundOperator, "op".into())
+    }
+
+    /// Get the class names used by this renderer.
+    #[inline]
+    pub const fn theme(&self) -> &Theme<SmolStr> {
+        &self.theme
+    }
+
+    /// Render the passed source code, which must be the code the tokens were
+    /// lexed from.
+    pub fn render(&self, source: &str, tokens: &[Token]) -> String {
+        let mut output = String::with_capacity(source.len() * 2);
+
+        for (kind, text) in segments(source, tokens) {
+            match kind.and_then(|kind| self.theme.get(kind)) {
+                Some(class) => {
+                    output.push_str("<span class=\"");
+                    HtmlEscape::Full.write(&mut output, class);
+                    output.push_str("\">");
+                    self.escape.write(&mut output, text);
+                    output.push_str("</span>");
+                }
+                None => self.escape.write(&mut output, text),
+            }
+        }
+
+        output
+    }
+}
+
+impl Default for HtmlRenderer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,175 @@
+//! Syntax highlighting renderers for lexed code.
+
+use lsp_types::Position;
+
+use crate::prelude::{Token, TokenKind, TokenType, Trivia};
+
+crate_reexport!(html, ansi);
+
+/// A theme, mapping each [`TokenKind`] to an optional style. Comments in trivia
+/// use the style of [`TokenKind::Comment`], spaces are never styled.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Theme<T> {
+    /// The style of each kind, indexed by the kind's discriminant.
+    styles: [Option<T>; TokenKind::ALL.len()],
+}
+
+impl<T> Theme<T> {
+    /// Create a new [`Theme`] without any styles.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            styles: [const { None }; TokenKind::ALL.len()],
+        }
+    }
+
+    /// Set the style of the passed kind. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub fn with(mut self, kind: TokenKind, style: T) -> Self {
+        self.set(kind, style);
+        self
+    }
+
+    /// Set the style of the passed kind.
+    #[inline]
+    pub fn set(&mut self, kind: TokenKind, style: T) {
+        self.styles[kind as usize] = Some(style);
+    }
+
+    /// Remove the style of the passed kind.
+    #[inline]
+    pub const fn remove(&mut self, kind: TokenKind) -> Option<T> {
+        self.styles[kind as usize].take()
+    }
+
+    /// Get the style of the passed kind.
+    #[inline]
+    pub const fn get(&self, kind: TokenKind) -> Option<&T> {
+        self.styles[kind as usize].as_ref()
+    }
+}
+
+impl<T> Default for Theme<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A piece of the source code, with the kind to highlight it as. Spaces have
+/// no kind.
+pub(crate) type Segment<'a> = (Option<TokenKind>, &'a str);
+
+/// Splits source code into [`segments`](Segment).
+struct Segmenter<'a> {
+    /// The source code.
+    source: &'a str,
+
+    /// The byte offset of the next segment.
+    cursor: usize,
+
+    /// The byte offset and [`Position`] of the last located position, positions
+    /// are always located in order.
+    located: (usize, Position),
+
+    /// The segments found so far.
+    segments: Vec<Segment<'a>>,
+}
+
+impl<'a> Segmenter<'a> {
+    /// Get the byte offset of the passed position, which must be after the last
+    /// located one.
+    fn locate(&mut self, position: Position) -> usize {
+        let (mut offset, mut current) = self.located;
+
+        for character in self.source[offset..].chars() {
+            if current >= position {
+                break;
+            }
+
+            offset += character.len_utf8();
+            if character == '\n' {
+                current.line += 1;
+                current.character = 0;
+            } else {
+                current.character += 1;
+            }
+        }
+
+        self.located = (offset, current);
+        offset
+    }
+
+    /// Add a segment going until the passed byte offset.
+    fn push(&mut self, kind: Option<TokenKind>, end: usize) {
+        let end = end.clamp(self.cursor, self.source.len());
+
+        if end > self.cursor {
+            self.segments.push((kind, &self.source[self.cursor..end]));
+        }
+        self.cursor = end;
+    }
+
+    /// Add a segment for each of the passed trivia, followed by an unstyled one
+    /// going until the passed byte offset.
+    fn push_trivia(&mut self, trivia: &[Trivia], end: usize) {
+        for trivia in trivia {
+            let kind = trivia.is_comment().then_some(TokenKind::Comment);
+            self.push(kind, trivia.end_offset.min(end));
+        }
+
+        self.push(None, end);
+    }
+
+    /// Add a segment for the passed token, ending where its trailing trivia
+    /// start, which go until the passed byte offset.
+    fn push_token(&mut self, token: &Token, end: usize) {
+        let text_end = token
+            .trailing_trivia
+            .first()
+            .map_or(end, |trivia| trivia.start_offset);
+
+        self.push(Some(token.token_type.kind()), text_end);
+        self.push_trivia(&token.trailing_trivia, end);
+    }
+}
+
+/// Split the source code into [`segments`](Segment) using the passed tokens.
+/// The segments, when joined, always form the full source code. Each token goes
+/// from where it starts to where the next one does, minus its trailing trivia.
+pub(crate) fn segments<'a>(source: &'a str, tokens: &[Token]) -> Vec<Segment<'a>> {
+    let mut segmenter = Segmenter {
+        source,
+        cursor: 0,
+        located: (0, Position::default()),
+        segments: Vec::with_capacity(tokens.len() * 2),
+    };
+
+    let mut previous: Option<&Token> = None;
+    for token in tokens {
+        // Errors met while lexing a token are returned after it, with the range
+        // of the text they're about. They don't own any text themselves.
+        if matches!(token.token_type, TokenType::Error(_))
+            && (token.start == token.end
+                || previous.is_some_and(|previous| token.start < previous.end))
+        {
+            continue;
+        }
+
+        let start = segmenter.locate(token.start);
+        match previous {
+            Some(previous) => segmenter.push_token(previous, start),
+            None => segmenter.push_trivia(&token.leading_trivia, start),
+        }
+
+        previous = Some(token);
+    }
+
+    match previous {
+        Some(previous) => segmenter.push_token(previous, source.len()),
+        None => segmenter.push(None, source.len()),
+    }
+
+    segmenter.segments
+}
@@ -0,0 +1,362 @@
+//! A stable and versioned serialization model for tokens, meant for tools
+//! which don't use this crate, like editor extensions in other languages.
+//!
+//! Unlike the `serde` implementations of [`Token`](crate::token::Token) and
+//! [`TokenType`], which follow the crate's types, this model only changes along
+//! with [`SCHEMA_VERSION`]. Adding fields is not considered a breaking change,
+//! but renaming or removing fields and kinds is. The JSON Schema of
+//! [`TokenDump`] is checked in at `schema/tokens.schema.json` and can be
+//! generated with `json_schema`, using the `schema` feature.
+
+use lsp_types::Position;
+use std::{borrow::Cow, iter};
+
+use crate::prelude::{
+    Error, Lexer, LexerInput, Severity, TokenKind, TokenStore, TokenType, Trivia, TriviaType,
+};
+
+/// The version of the serialization model. It's bumped with every breaking
+/// change.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// All tokens of a file.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TokenDump {
+    /// The version of the model, [`SCHEMA_VERSION`].
+    pub schema_version: u32,
+
+    /// The tokens, the last one being of kind `end_of_file`.
+    pub tokens: Vec<TokenRecord>,
+}
+
+/// A single token.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TokenRecord {
+    /// What the token is.
+    pub kind: RecordKind,
+
+    /// The exact text of the token in the source, excluding trivia.
+    pub text: String,
+
+    /// Where the token is, excluding trivia. Errors reported for an already
+    /// lexed token are empty tokens after it, see [`TokenRecord::error`].
+    pub range: SourceRange,
+
+    /// The error, if the token is of kind `error`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorRecord>,
+
+    /// The trivia before the token. This is the same as the trailing trivia
+    /// of the previous token.
+    pub leading_trivia: Vec<TriviaRecord>,
+
+    /// The trivia after the token.
+    pub trailing_trivia: Vec<TriviaRecord>,
+}
+
+/// The kind of a [`TokenRecord`].
+#[derive(
+    Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RecordKind {
+    /// An error, see [`TokenRecord::error`].
+    Error,
+
+    /// A number, like `1`, `0x1F`, or `0b101`.
+    Number,
+
+    /// A string, with its quotes.
+    String,
+
+    /// `true` or `false`.
+    Boolean,
+
+    /// An identifier, like a variable name.
+    Identifier,
+
+    /// A comment, only when comments are lexed as tokens.
+    Comment,
+
+    /// A reserved keyword, like `local`.
+    Keyword,
+
+    /// A word that can both be a keyword and an identifier, like `type`.
+    PartialKeyword,
+
+    /// A symbol, like `(` and `->`.
+    Symbol,
+
+    /// An operator, like `+` and `and`.
+    Operator,
+
+    /// A compound operator, like `+=` and `==`.
+    CompoundOperator,
+
+    /// The end of the file.
+    EndOfFile,
+
+    /// Spaces, only when spaces are lexed as tokens.
+    Spaces,
+}
+
+impl From<TokenKind> for RecordKind {
+    fn from(kind: TokenKind) -> Self {
+        match kind {
+            TokenKind::Error => Self::Error,
+            TokenKind::Number => Self::Number,
+            TokenKind::String => Self::String,
+            TokenKind::Boolean => Self::Boolean,
+            TokenKind::Identifier => Self::Identifier,
+            TokenKind::Comment => Self::Comment,
+            TokenKind::Keyword => Self::Keyword,
+            TokenKind::PartialKeyword => Self::PartialKeyword,
+            TokenKind::Symbol => Self::Symbol,
+            TokenKind::Operator => Self::Operator,
+            TokenKind::CompoundOperator => Self::CompoundOperator,
+            TokenKind::EndOfFile => Self::EndOfFile,
+            TokenKind::Spaces => Self::Spaces,
+        }
+    }
+}
+
+/// Spaces or a comment around a token.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TriviaRecord {
+    /// What the trivia is.
+    pub kind: TriviaRecordKind,
+
+    /// The exact text of the trivia in the source.
+    pub text: String,
+
+    /// Where the trivia is.
+    pub range: SourceRange,
+}
+
+/// The kind of a [`TriviaRecord`].
+#[derive(
+    Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TriviaRecordKind {
+    /// Whitespace, including new lines.
+    Whitespace,
+
+    /// A single or multi-line comment.
+    Comment,
+}
+
+impl From<&Trivia> for TriviaRecord {
+    fn from(trivia: &Trivia) -> Self {
+        Self {
+            kind: match trivia.trivia_type {
+                TriviaType::Spaces(_) => TriviaRecordKind::Whitespace,
+                TriviaType::Comment(_) => TriviaRecordKind::Comment,
+            },
+            text: trivia.as_str().to_string(),
+            range: SourceRange {
+                start: SourceLocation::new(trivia.start_offset, trivia.start),
+                end: SourceLocation::new(trivia.end_offset, trivia.end),
+            },
+        }
+    }
+}
+
+/// An error met while lexing.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ErrorRecord {
+    /// The error message.
+    pub message: String,
+
+    /// How severe the error is.
+    pub severity: RecordSeverity,
+
+    /// The code the error is about.
+    pub range: SourceRange,
+}
+
+/// The severity of an [`ErrorRecord`].
+#[derive(
+    Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RecordSeverity {
+    /// The code is invalid.
+    Error,
+
+    /// The code is valid but might not behave as intended.
+    Warning,
+}
+
+/// A range in the source, the end being exclusive.
+#[derive(
+    Clone, Copy, Debug, Default, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SourceRange {
+    /// Where the range starts.
+    pub start: SourceLocation,
+
+    /// Where the range ends.
+    pub end: SourceLocation,
+}
+
+/// A location in the source.
+#[derive(
+    Clone, Copy, Debug, Default, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SourceLocation {
+    /// The byte offset from the start of the source.
+    pub offset: usize,
+
+    /// The zero-based line.
+    pub line: u32,
+
+    /// The zero-based column, in characters, with tabs taking as many columns
+    /// as the lexer's tab width.
+    pub column: u32,
+}
+
+impl SourceLocation {
+    /// Create a new [`SourceLocation`] at the passed offset and position.
+    #[inline]
+    pub const fn new(offset: usize, position: Position) -> Self {
+        Self {
+            offset,
+            line: position.line,
+            column: position.character,
+        }
+    }
+}
+
+impl TokenDump {
+    /// Lex the passed code and serialize its tokens.
+    #[inline]
+    pub fn new(code: &str) -> Self {
+        Self::from_lexer(&mut Lexer::new(code))
+    }
+
+    /// Serialize all remaining tokens in the passed lexer. The lexer is
+    /// expected to be at the start of its input.
+    pub fn from_lexer<I: LexerInput>(lexer: &mut Lexer<I>) -> Self {
+        let store = TokenStore::from_lexer(lexer);
+        let input = lexer.input();
+        let source = input.slice(0..input.len());
+        let lines = LineIndex::new(&source, store.tab_width());
+
+        let mut tokens = Vec::with_capacity(store.len());
+
+        for (index, token) in store.tokens().iter().enumerate() {
+            let Some(range) = store.range(index) else {
+                continue;
+            };
+            let start = SourceLocation::new(range.start, token.start);
+            let end = SourceLocation::new(range.end, token.end);
+
+            let error = match &token.token_type {
+                TokenType::Error(error) => Some(lines.error(error)),
+                _ => None,
+            };
+
+            tokens.push(TokenRecord {
+                kind: token.token_type.kind().into(),
+                text: source.get(range).unwrap_or_default().to_string(),
+                range: SourceRange { start, end },
+                error,
+                leading_trivia: token.leading_trivia.iter().map(Into::into).collect(),
+                trailing_trivia: token.trailing_trivia.iter().map(Into::into).collect(),
+            });
+        }
+
+        Self {
+            schema_version: SCHEMA_VERSION,
+            tokens,
+        }
+    }
+}
+
+/// Converts [`Position`]s back to byte offsets.
+struct LineIndex<'a> {
+    /// The source.
+    source: &'a Cow<'a, str>,
+
+    /// The byte offset at which each line starts.
+    starts: Vec<usize>,
+
+    /// How many columns a tab takes.
+    tab_width: u32,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Index the lines of the passed source.
+    fn new(source: &'a Cow<'a, str>, tab_width: u32) -> Self {
+        let starts = iter::once(0)
+            .chain(source.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+
+        Self {
+            source,
+            starts,
+            tab_width,
+        }
+    }
+
+    /// Get the [`SourceLocation`] at the passed position.
+    fn location(&self, position: Position) -> SourceLocation {
+        let Some(&line_start) = self.starts.get(position.line as usize) else {
+            return SourceLocation::new(self.source.len(), position);
+        };
+
+        let mut column = 0;
+        let offset = self.source[line_start..]
+            .char_indices()
+            .find(|(_, character)| {
+                if column >= position.character || *character == '\n' {
+                    return true;
+                }
+
+                column += if *character == '\t' {
+                    self.tab_width
+                } else {
+                    1
+                };
+                false
+            })
+            .map_or(self.source.len(), |(offset, _)| line_start + offset);
+
+        SourceLocation::new(offset, position)
+    }
+
+    /// Serialize the passed error.
+    fn error(&self, error: &Error) -> ErrorRecord {
+        let start = error.start();
+
+        ErrorRecord {
+            message: error.message().to_string(),
+            severity: match error.severity() {
+                Severity::Error => RecordSeverity::Error,
+                Severity::Warning => RecordSeverity::Warning,
+            },
+            range: SourceRange {
+                start: self.location(start),
+                end: self.location(error.end().unwrap_or(start)),
+            },
+        }
+    }
+}
+
+/// Get the JSON Schema of [`TokenDump`].
+#[cfg(feature = "schema")]
+#[inline]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub fn json_schema() -> schemars::Schema {
+    schemars::schema_for!(TokenDump)
+}
@@ -8,7 +8,7 @@ use crate::token::Trivia;
 #[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct State {
-    /// The current character position.
+    /// The current byte offset in the input.
     pub(crate) position: usize,
 
     /// The current [`position`](Position) in the file.
@@ -16,12 +16,21 @@ pub struct State {
 
     /// The spaces after the last parsed token.
     pub(crate) last_trivia: Vec<Trivia>,
+
+    /// The byte offset at which [`State::last_trivia`] starts.
+    pub(crate) trivia_start: usize,
+
+    /// The [`Position`] at which [`State::last_trivia`] starts.
+    pub(crate) trivia_start_position: Position,
+
+    /// The number of errors, excluding warnings, reported so far.
+    pub(crate) errors_count: usize,
 }
 
 impl State {
     /// Move the state by the passed character.
     pub const fn increment_position_by_char(&mut self, character: char) {
-        self.position += 1;
+        self.position += character.len_utf8();
 
         match character {
             '\n' => {
@@ -32,12 +41,19 @@ impl State {
         }
     }
 
-    /// Move th state ahead by the passed amount of characters.
+    /// Move the state ahead by the passed amount of characters. They must all
+    /// be ascii and not new lines.
     pub const fn increment_position(&mut self, amount: u32) {
         self.position += amount as usize;
         self.lexer_position.character += amount;
     }
 
+    /// Get the current byte offset in the input.
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.position
+    }
+
     /// Get the current file [`position`](Position).
     #[inline]
     pub const fn lexer_position(&self) -> Position {
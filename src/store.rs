@@ -0,0 +1,309 @@
+//! The [`TokenStore`] struct.
+
+use lsp_types::Position;
+use std::ops::Range;
+
+use crate::prelude::{Lexer, LexerInput, Token, TokenType, Trivia};
+
+/// All tokens of a file along with their byte ranges, allowing fast lookups of
+/// the token at a specific [`Position`] or byte offset.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct TokenStore {
+    /// The tokens, the last one being [`TokenType::EndOfFile`].
+    tokens: Vec<Token>,
+
+    /// The byte range of each token, excluding its trivia.
+    ranges: Vec<Range<usize>>,
+
+    /// How many columns a tab takes, as used by the lexer.
+    tab_width: u32,
+}
+
+/// Which side wins when looking up a position exactly between two items, like
+/// the end of one token and the start of the next one.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Bias {
+    /// The item before the position.
+    Left,
+
+    /// The item after the position.
+    #[default]
+    Right,
+}
+
+/// Where exactly a position is relative to the token found by
+/// [`TokenStore::at_position`] and [`TokenStore::at_offset`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TokenLocation {
+    /// In the token itself.
+    Token,
+
+    /// In the token's leading trivia, at the passed index. This only happens
+    /// for the first token as trivia between two tokens is considered trailing.
+    LeadingTrivia(usize),
+
+    /// In the token's trailing trivia, at the passed index.
+    TrailingTrivia(usize),
+}
+
+/// The result of looking up a position in a [`TokenStore`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TokenAt<'a> {
+    /// The index of the token.
+    pub index: usize,
+
+    /// The token.
+    pub token: &'a Token,
+
+    /// Where the position is relative to the token.
+    pub location: TokenLocation,
+}
+
+impl TokenAt<'_> {
+    /// Get the trivia the position is in, if any.
+    pub fn trivia(&self) -> Option<&Trivia> {
+        match self.location {
+            TokenLocation::Token => None,
+            TokenLocation::LeadingTrivia(index) => self.token.leading_trivia.get(index),
+            TokenLocation::TrailingTrivia(index) => self.token.trailing_trivia.get(index),
+        }
+    }
+
+    /// Whether or not the position is in a comment, be it trivia or a
+    /// [`TokenType::Comment`].
+    pub fn is_in_comment(&self) -> bool {
+        match self.location {
+            TokenLocation::Token => matches!(self.token.token_type, TokenType::Comment(_)),
+            _ => self.trivia().is_some_and(Trivia::is_comment),
+        }
+    }
+}
+
+impl TokenStore {
+    /// Lex the passed code and store its tokens.
+    #[inline]
+    pub fn new(code: &str) -> Self {
+        Self::from_lexer(&mut Lexer::new(code))
+    }
+
+    /// Store all remaining tokens in the passed lexer. The lexer is expected
+    /// to be at the start of its input.
+    pub fn from_lexer<I: LexerInput>(lexer: &mut Lexer<I>) -> Self {
+        let mut tokens = Vec::new();
+        let mut ranges = Vec::new();
+
+        loop {
+            let start = lexer.offset();
+            let token = lexer.next_token();
+
+            // Zero-width tokens may still move the lexer, like when it stops
+            // after too many errors.
+            let end = if token.start == token.end {
+                start
+            } else {
+                token
+                    .trailing_trivia
+                    .first()
+                    .map_or_else(|| lexer.offset(), |trivia| trivia.start_offset)
+            };
+
+            let is_done = token == TokenType::EndOfFile;
+
+            tokens.push(token);
+            ranges.push(start..end);
+
+            if is_done {
+                break;
+            }
+        }
+
+        Self {
+            tokens,
+            ranges,
+            tab_width: lexer.config().tab_width(),
+        }
+    }
+
+    /// Get all tokens.
+    #[inline]
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Get the token at the passed index.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Token> {
+        self.tokens.get(index)
+    }
+
+    /// Get the byte range of the token at the passed index, excluding its
+    /// trivia.
+    #[inline]
+    pub fn range(&self, index: usize) -> Option<Range<usize>> {
+        self.ranges.get(index).cloned()
+    }
+
+    /// The number of tokens, including [`TokenType::EndOfFile`].
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Get how many columns a tab takes in the stored positions.
+    #[inline]
+    pub const fn tab_width(&self) -> u32 {
+        self.tab_width
+    }
+
+    /// Whether or not there are no tokens at all.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Get the index of the closest token before the passed index which isn't
+    /// a [`TokenType::Comment`], [`TokenType::Spaces`], or a zero-width error.
+    pub fn previous(&self, index: usize) -> Option<usize> {
+        (0..index.min(self.len()))
+            .rev()
+            .find(|index| self.is_significant(*index))
+    }
+
+    /// Get the index of the closest token after the passed index which isn't
+    /// a [`TokenType::Comment`], [`TokenType::Spaces`], or a zero-width error.
+    pub fn next(&self, index: usize) -> Option<usize> {
+        (index + 1..self.len()).find(|index| self.is_significant(*index))
+    }
+
+    /// Whether or not the token at the passed index isn't a comment, spaces, or
+    /// an error which doesn't own any text.
+    fn is_significant(&self, index: usize) -> bool {
+        let token = &self.tokens[index];
+
+        match token.token_type {
+            TokenType::Comment(_) | TokenType::Spaces(_) => false,
+            TokenType::Error(_) => token.start != token.end,
+            _ => true,
+        }
+    }
+
+    /// Get the token at the passed [`Position`].
+    pub fn at_position(&self, position: Position, bias: Bias) -> Option<TokenAt<'_>> {
+        self.find(
+            position,
+            bias,
+            |index| (self.tokens[index].start, self.tokens[index].end),
+            |trivia| trivia.end,
+            Position::default(),
+        )
+    }
+
+    /// Get the token at the passed byte offset.
+    pub fn at_offset(&self, offset: usize, bias: Bias) -> Option<TokenAt<'_>> {
+        self.find(
+            offset,
+            bias,
+            |index| (self.ranges[index].start, self.ranges[index].end),
+            |trivia| trivia.end_offset,
+            0,
+        )
+    }
+
+    /// Find the token at the passed key, which is either a position or an
+    /// offset. The key is in `start..end` when it's after `start` (or equal to
+    /// it with [`Bias::Right`]) and before `end` (or equal to it with
+    /// [`Bias::Left`]).
+    fn find<K: Copy + Ord>(
+        &self,
+        key: K,
+        bias: Bias,
+        bounds: impl Fn(usize) -> (K, K),
+        trivia_end: impl Fn(&Trivia) -> K,
+        file_start: K,
+    ) -> Option<TokenAt<'_>> {
+        let is_after = |start: K| match bias {
+            Bias::Left => key > start,
+            Bias::Right => key >= start,
+        };
+        let is_before = |end: K| match bias {
+            Bias::Left => key <= end,
+            Bias::Right => key < end,
+        };
+
+        // Tokens are sorted so a binary search finds the last token starting
+        // before the key. Zero-width tokens are skipped as they own no text.
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if is_after(bounds(middle).0) {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        let candidate = (0..low).rev().find(|index| {
+            let (start, end) = bounds(*index);
+            start != end
+        });
+
+        let Some(index) = candidate else {
+            // There's nothing before the very start of the file.
+            if bias == Bias::Left && key <= file_start {
+                return self.find(key, Bias::Right, bounds, trivia_end, file_start);
+            }
+
+            let token = self.tokens.first()?;
+            let location = find_trivia(&token.leading_trivia, is_before, trivia_end)
+                .map_or(TokenLocation::Token, TokenLocation::LeadingTrivia);
+
+            return Some(TokenAt {
+                index: 0,
+                token,
+                location,
+            });
+        };
+
+        let token = &self.tokens[index];
+        let (_, end) = bounds(index);
+        if is_before(end) {
+            return Some(TokenAt {
+                index,
+                token,
+                location: TokenLocation::Token,
+            });
+        }
+
+        if let Some(trivia_index) = find_trivia(&token.trailing_trivia, is_before, trivia_end) {
+            return Some(TokenAt {
+                index,
+                token,
+                location: TokenLocation::TrailingTrivia(trivia_index),
+            });
+        }
+
+        // Past the end of the file.
+        let index = self.len() - 1;
+        Some(TokenAt {
+            index,
+            token: &self.tokens[index],
+            location: TokenLocation::Token,
+        })
+    }
+}
+
+/// Find the index of the trivia which ends after the key, as decided by
+/// `is_before`, if the trivia starts at `start`.
+fn find_trivia<K: Copy>(
+    trivia: &[Trivia],
+    is_before: impl Fn(K) -> bool,
+    trivia_end: impl Fn(&Trivia) -> K,
+) -> Option<usize> {
+    trivia
+        .iter()
+        .position(|trivia| is_before(trivia_end(trivia)))
+}
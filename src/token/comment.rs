@@ -2,7 +2,7 @@
 
 use smol_str::SmolStr;
 
-use crate::prelude::{Lexable, Lexer, LuauString};
+use crate::prelude::{Lexable, Lexer, LexerInput, LuauString};
 
 /// A comment.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -26,31 +26,38 @@ pub enum Comment {
 }
 
 impl Comment {
+    /// Get the comment as a string. This includes the `--`.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::SingleLine(comment) | Self::MultiLine(comment) => comment,
+        }
+    }
+
     /// Parses a [`Comment::SingleLine`].
-    fn parse_inner(lexer: &mut Lexer) -> SmolStr {
-        let mut characters = vec!['-', '-'];
+    fn parse_inner<I: LexerInput>(lexer: &mut Lexer<I>, start: usize) -> SmolStr {
+        let end = lexer
+            .input
+            .find_byte(lexer.position, b"\n\r")
+            .unwrap_or_else(|| lexer.input.len());
 
-        while let Some(character) = lexer.current_char() {
-            if character == '\n' || character == '\r'  {
-                break;
-            }
+        lexer.advance_to(end);
 
-            characters.push(character);
-            lexer.increment_position_by_char(character);
-        }
-
-        SmolStr::from_iter(characters)
+        lexer.slice_from(start)
     }
 }
 
 impl Lexable for Comment {
-    fn try_lex(lexer: &mut Lexer) -> Option<Self> {
+    fn try_lex<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self> {
+        // The `--` is already consumed.
+        let start = lexer.position - 2;
+
         if lexer.current_char() == Some('[') {
-            Some(Self::MultiLine(
-                format!("--{}", LuauString::parse_multi_line(lexer)).into(),
-            ))
+            LuauString::skip_multi_line(lexer);
+
+            Some(Self::MultiLine(lexer.slice_from(start)))
         } else {
-            Some(Self::SingleLine(Self::parse_inner(lexer)))
+            Some(Self::SingleLine(Self::parse_inner(lexer, start)))
         }
     }
 }
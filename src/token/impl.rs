@@ -1,19 +1,21 @@
 //! All `impl` blocks for [`TokenType`]
 
-use crate::{
-    prelude::{
-        Comment, CompoundOperator, Keyword, Lexable, Lexer, Literal, Operator, Error,
-        PartialKeyword, Symbol, TokenType,
-    },
-    utils::is_identifier_start,
+use crate::prelude::{
+    Comment, CompoundOperator, Keyword, Lexable, Lexer, LexerInput, Literal, Operator, Error,
+    PartialKeyword, Symbol, TokenType,
 };
 
 impl Lexable for TokenType {
-    fn try_lex(lexer: &mut Lexer) -> Option<Self> {
+    fn try_lex<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self> {
         let character = lexer.current_char()?;
         let start = lexer.lexer_position;
 
         match character {
+            _ if lexer.config.spaces_as_tokens
+                && lexer.config.whitespace.is_whitespace(character) =>
+            {
+                return Some(Self::Spaces(lexer.skip_whitespace()));
+            }
             '0'..='9' => {
                 if let Some(number) = Literal::parse_number(lexer) {
                     return Some(Self::Literal(number));
@@ -21,10 +23,10 @@ impl Lexable for TokenType {
             }
             '.' => {
                 let next_character = lexer.next_char();
-                if matches!(next_character, Some('0'..='9')) {
-                    if let Some(number) = Literal::parse_number(lexer) {
-                        return Some(Self::Literal(number));
-                    }
+                if matches!(next_character, Some('0'..='9'))
+                    && let Some(number) = Literal::parse_number(lexer)
+                {
+                    return Some(Self::Literal(number));
                 }
                 lexer.consume('.');
 
@@ -45,16 +47,21 @@ impl Lexable for TokenType {
                     return Some(Self::Symbol(Symbol::Dot));
                 }
             }
-            '\'' | '"' | '`' => {
+            '\'' | '"' => {
+                if let Some(string) = Literal::parse_string(lexer) {
+                    return Some(Self::Literal(string));
+                }
+            }
+            '`' if lexer.dialect().is_luau() => {
                 if let Some(string) = Literal::parse_string(lexer) {
                     return Some(Self::Literal(string));
                 }
             }
             '[' => {
-                if matches!(lexer.next_char(), Some('[' | '=')) {
-                    if let Some(string) = Literal::parse_string(lexer) {
-                        return Some(Self::Literal(string));
-                    }
+                if matches!(lexer.next_char(), Some('[' | '='))
+                    && let Some(string) = Literal::parse_string(lexer)
+                {
+                    return Some(Self::Literal(string));
                 }
                 lexer.consume('[');
 
@@ -68,10 +75,16 @@ impl Lexable for TokenType {
             '<' if lexer.consume_with_next('=') => {
                 return Some(Self::CompoundOperator(CompoundOperator::LessThanOrEqualTo));
             }
+            '<' if lexer.dialect().has_bitwise_operators() && lexer.consume_with_next('<') => {
+                return Some(Self::Operator(Operator::LeftShift));
+            }
+            '>' if lexer.dialect().has_bitwise_operators() && lexer.consume_with_next('>') => {
+                return Some(Self::Operator(Operator::RightShift));
+            }
             '-' if lexer.consume_with_next('-') => {
                 return Comment::try_lex(lexer).map(Self::Comment)
             }
-            '-' if lexer.consume_with_next('>') => {
+            '-' if lexer.dialect().is_luau() && lexer.consume_with_next('>') => {
                 return Some(Self::Symbol(Symbol::Arrow));
             }
             '=' => {
@@ -83,26 +96,42 @@ impl Lexable for TokenType {
                     return Some(Self::Symbol(Symbol::Equal));
                 }
             }
-            ':' if lexer.consume_with_next(':') => {
+            ':' if lexer.dialect().is_luau() && lexer.consume_with_next(':') => {
                 return Some(Self::Symbol(Symbol::Typecast));
             }
-            _ if is_identifier_start(character) => {
-                let word = lexer.consume_identifier();
+            ':' if lexer.dialect().has_labels() && lexer.consume_with_next(':') => {
+                return Some(Self::Symbol(Symbol::DoubleColon));
+            }
+            '@' if !lexer.dialect().is_luau() => (),
+            _ if lexer.config.identifiers.is_identifier_start(character) => {
+                let start = lexer.position;
+                lexer.skip_identifier();
 
-                if let Some(keyword) = Keyword::try_from_str(&word) {
-                    return Some(Self::Keyword(keyword));
-                } else if let Some(partial_keyword) = PartialKeyword::try_from_str(&word) {
-                    return Some(Self::PartialKeyword(partial_keyword));
-                }
+                // Words are checked straight from the input, only identifiers
+                // get their own string.
+                let token_type = {
+                    let word = lexer.input.slice(start..lexer.position);
+                    let dialect = lexer.dialect();
 
-                match word.as_str() {
-                    "true" => return Some(Self::Literal(Literal::Boolean(true))),
-                    "false" => return Some(Self::Literal(Literal::Boolean(false))),
-                    "and" => return Some(Self::Operator(Operator::And)),
-                    "or" => return Some(Self::Operator(Operator::Or)),
-                    "not" => return Some(Self::Operator(Operator::Not)),
-                    _ => return Some(Self::Identifier(word)),
-                }
+                    Keyword::try_from_str_in(&word, dialect)
+                        .map(Self::Keyword)
+                        .or_else(|| {
+                            PartialKeyword::try_from_str_in(&word, dialect)
+                                .map(Self::PartialKeyword)
+                        })
+                        .or(match &*word {
+                            "true" => Some(Self::Literal(Literal::Boolean(true))),
+                            "false" => Some(Self::Literal(Literal::Boolean(false))),
+                            "and" => Some(Self::Operator(Operator::And)),
+                            "or" => Some(Self::Operator(Operator::Or)),
+                            "not" => Some(Self::Operator(Operator::Not)),
+                            _ => None,
+                        })
+                };
+
+                return Some(
+                    token_type.unwrap_or_else(|| Self::Identifier(lexer.intern_from(start))),
+                );
             }
             _ => {
                 if let Some(symbol) = Symbol::try_from_char(character, lexer) {
@@ -116,7 +145,7 @@ impl Lexable for TokenType {
             }
         }
 
-        lexer.increment_position(1);
+        lexer.increment_position_by_char(character);
 
         Some(Self::Error(Error::new(
             start,
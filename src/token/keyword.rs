@@ -1,9 +1,16 @@
 //! [`Keyword`] and [`PartialKeyword`] enums.
 
-/// A macro to generate an enum for keywords.
+/// A macro to generate an enum for keywords. Each keyword can be followed by
+/// `in [Dialect, ...]` to only lex it in these [`dialects`](crate::dialect::Dialect),
+/// keywords without it are in all dialects.
 macro_rules! generate_keyword_enum {
+    (@in $dialect: ident) => { true };
+    (@in $dialect: ident $($dialects: ident)+) => {
+        matches!($dialect, $( crate::dialect::Dialect::$dialects )|+)
+    };
+
     ($(#[$meta:meta])* $vis:vis enum $struct: ident {
-        $( $(#[$name_meta:meta])* $str: literal => $name: ident ),* $(,)?
+        $( $(#[$name_meta:meta])* $str: literal => $name: ident $(in [$($dialects: ident),+])? ),* $(,)?
     }) => {
         $(#[$meta])*
         #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -13,25 +20,75 @@ macro_rules! generate_keyword_enum {
         }
 
         impl $struct {
-            /// Try creating this item from a string.
+            /// All items, in declaration order.
+            pub const ALL: [Self; [$(Self::$name),*].len()] = [$(Self::$name),*];
+
+            /// Try creating this item from a string, in [`Dialect::Luau`](crate::dialect::Dialect::Luau).
+            #[inline]
             pub fn try_from_str(value: &str) -> Option<Self> {
-                match value {
-                    $( $str => Some(Self::$name), )*
-                    _ => None,
+                Self::try_from_str_in(value, crate::dialect::Dialect::Luau)
+            }
+
+            /// Every item, placed at the index given by [`keyword_hash`]. The
+            /// hash is checked at compile time to never collide.
+            const TABLE: [Option<Self>; KEYWORD_TABLE_SIZE] = {
+                let mut table = [None; KEYWORD_TABLE_SIZE];
+
+                $(
+                    let index = keyword_hash(Self::$name.as_str().as_bytes());
+                    assert!(table[index].is_none(), "Keywords must have different hashes.");
+                    table[index] = Some(Self::$name);
+                )*
+
+                table
+            };
+
+            /// Try creating this item from a string, in the passed dialect.
+            pub fn try_from_str_in(value: &str, dialect: crate::dialect::Dialect) -> Option<Self> {
+                let keyword = Self::TABLE[keyword_hash(value.as_bytes())]?;
+
+                (keyword.as_str() == value && keyword.is_in(dialect)).then_some(keyword)
+            }
+
+            /// Get the item as a string.
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $( Self::$name => $str, )*
+                }
+            }
+
+            /// Whether or not this item exists in the passed dialect.
+            pub const fn is_in(self, dialect: crate::dialect::Dialect) -> bool {
+                match self {
+                    $( Self::$name => generate_keyword_enum!(@in dialect $($($dialects)+)?), )*
                 }
             }
         }
 
         impl std::fmt::Display for $struct {
+            #[inline]
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                f.write_str(match self {
-                    $( Self::$name => $str, )*
-                })
+                f.write_str(self.as_str())
             }
         }
     };
 }
 
+/// The size of the lookup tables of [`Keyword`] and [`PartialKeyword`].
+const KEYWORD_TABLE_SIZE: usize = 32;
+
+/// A perfect hash of the keywords, only relying on their length and their first
+/// and last bytes, so looking a word up is a single comparison.
+#[inline]
+const fn keyword_hash(word: &[u8]) -> usize {
+    match word {
+        [] => 0,
+        [first, .., last] | [first @ last] => {
+            (word.len() * 2 + *first as usize + *last as usize * 6) % KEYWORD_TABLE_SIZE
+        }
+    }
+}
+
 generate_keyword_enum!(
     /// Words that can only be used as be keywords. Check [`PartialKeyword`].
     pub enum Keyword {
@@ -82,6 +139,9 @@ generate_keyword_enum!(
 
         /// The `nil` keyword.
         "nil" => Nil,
+
+        /// The `goto` keyword, only in Lua 5.2 and later.
+        "goto" => Goto in [Lua52, Lua53, Lua54],
     }
 );
 generate_keyword_enum!(
@@ -89,15 +149,21 @@ generate_keyword_enum!(
     /// Check [`Keyword`].
     pub enum PartialKeyword {
         /// The `type` keyword
-        "type" => Type,
+        "type" => Type in [Luau],
 
         /// The `continue` keyword
-        "continue" => Continue,
+        "continue" => Continue in [Luau],
 
         /// The `export` keyword
-        "export" => Export,
+        "export" => Export in [Luau],
 
         /// The `typeof` keyword
-        "typeof" => TypeOf,
+        "typeof" => TypeOf in [Luau],
+
+        /// The `const` attribute, as in `local x <const> = 1`, only in Lua 5.4.
+        "const" => Const in [Lua54],
+
+        /// The `close` attribute, as in `local x <close> = y`, only in Lua 5.4.
+        "close" => Close in [Lua54],
     }
 );
@@ -3,7 +3,7 @@
 use smol_str::SmolStr;
 
 use crate::{
-    prelude::{Lexable, Lexer, Error},
+    prelude::{Lexable, Lexer, LexerInput, Error},
     utils::is_numeric,
 };
 
@@ -41,85 +41,125 @@ pub enum LuauString {
     MultiLine(SmolStr),
 }
 
-impl LuauString {
-    /// Counts the number of backslashes at the end of the passed characters array.
-    fn count_back_slashes(characters: &[char]) -> usize {
-        if characters.is_empty() {
-            return 0;
-        }
+/// Tracks the backslashes before the end of a string while it's being lexed,
+/// to know which characters are escaped.
+#[derive(Clone, Copy, Debug, Default)]
+struct Escapes {
+    /// The number of backslashes right before the next character.
+    backslashes: usize,
 
-        let mut count = 0;
-        let mut i = characters.len() - 1;
-
-        while characters[i] == '\\' {
-            count += 1;
+    /// Whether or not the last character is escaped.
+    is_last_escaped: bool,
 
-            match i.checked_sub(1) {
-                Some(new_i) => i = new_i,
-                None => break,
-            }
-        }
+    /// Whether or not the last character is a new line.
+    is_last_new_line: bool,
+}
 
-        count
+impl Escapes {
+    /// Add the passed character, it only matters whether or not it's a `\\` or
+    /// a `\n`.
+    #[inline]
+    const fn push(&mut self, byte: u8) {
+        self.is_last_escaped = self.is_next_escaped();
+        self.backslashes = if byte == b'\\' {
+            self.backslashes + 1
+        } else {
+            0
+        };
+        self.is_last_new_line = byte == b'\n';
     }
 
-    /// Whether or not the last character is escaped.
-    fn is_escaped(characters: &[char]) -> bool {
-        if characters.len() < 2 {
-            false
-        } else {
-            Self::count_back_slashes(&characters[..characters.len() - 1]) % 2 != 0
+    /// Add characters which are neither `\\` nor new lines.
+    #[inline]
+    const fn skip(&mut self, is_single_character: bool) {
+        self.push(0);
+
+        if !is_single_character {
+            self.push(0);
         }
     }
 
-    /// Whether or not the array ends with a multiline escape character.
+    /// Whether or not the next character is escaped.
     #[inline]
-    fn is_multi_line_escaped(characters: &[char]) -> bool {
-        characters[characters.len() - 1] == '\n' // Been checked before. No need to recheck.
+    const fn is_next_escaped(self) -> bool {
+        !self.backslashes.is_multiple_of(2)
+    }
 
-            // No need to check for \z, turns out, it can be excluded. It only
-            // affects how the string is displayed when, say, printed, but in code,
-            // they're both the same.
-            || (Self::is_escaped(characters) /* && characters[characters.len() - 1] == 'z' */)
+    /// Whether or not a new line can be next, which is when the last character
+    /// is escaped or is itself a new line.
+    #[inline]
+    const fn allows_new_line(self) -> bool {
+        // No need to check for \z, turns out, it can be excluded. It only
+        // affects how the string is displayed when, say, printed, but in code,
+        // they're both the same.
+        self.is_last_new_line || self.is_last_escaped
     }
+}
 
+impl LuauString {
     /// Parses one of the single line variants:
     ///
     /// * [`LuauString::SingleQuotes`]
     /// * [`LuauString::DoubleQuotes`]
     /// * [`LuauString::Backticks`]
-    fn parse_inner(lexer: &mut Lexer, quote_character: char) -> SmolStr {
-        let mut characters = vec![quote_character];
+    fn parse_inner<I: LexerInput>(lexer: &mut Lexer<I>, quote_character: char) -> SmolStr {
         let start = lexer.lexer_position;
+        let start_offset = lexer.position;
+        let quote = quote_character as u8;
+        let mut escapes = Escapes::default();
+        let mut offset = start_offset + 1;
+        let mut is_single_line = true;
         let mut is_done = false;
 
-        lexer.increment_position_by_char(quote_character);
+        // Only these characters matter, everything between them is skipped.
+        while let Some(found) = lexer.input.find_byte(offset, &[quote, b'\\', b'\n', b'\r']) {
+            if found > offset {
+                let is_single_character = lexer
+                    .input
+                    .char_at(offset)
+                    .is_some_and(|character| character.len_utf8() == found - offset);
 
-        while let Some(character) = lexer.current_char() {
-            if (character == '\n' || character == '\r') && !Self::is_multi_line_escaped(&characters)
-            {
-                lexer.errors.push(Error::new(
-                    start,
-                    format!(
-                        "Strings must be single line, use `\\z` or `\\` here or add a {}.",
-                        quote_character
-                    ),
-                    Some(lexer.lexer_position),
-                ));
+                escapes.skip(is_single_character);
+            }
+
+            offset = found;
+
+            // SAFETY: `found` is the offset of an existing byte.
+            #[allow(clippy::unwrap_used)]
+            let byte = lexer.input.byte_at(found).unwrap();
+            if matches!(byte, b'\n' | b'\r') && !escapes.allows_new_line() {
+                is_single_line = false;
 
                 break;
             }
 
-            characters.push(character);
-            lexer.increment_position_by_char(character);
+            let is_escaped = escapes.is_next_escaped();
+            escapes.push(byte);
+            offset += 1;
 
-            if character == quote_character && !Self::is_escaped(&characters) {
+            if byte == quote && !is_escaped {
                 is_done = true;
 
                 break;
             }
         }
 
+        if is_single_line && !is_done {
+            offset = lexer.input.len();
+        }
+
+        lexer.advance_to(offset);
+
+        if !is_single_line {
+            lexer.errors.push(Error::new(
+                start,
+                format!(
+                    "Strings must be single line, use `\\z` or `\\` here or add a {}.",
+                    quote_character
+                ),
+                Some(lexer.lexer_position),
+            ));
+        }
         if !is_done {
             lexer.errors.push(Error::new(
                 start,
@@ -128,12 +168,12 @@ impl LuauString {
             ));
         }
 
-        SmolStr::from_iter(characters)
+        lexer.intern_from(start_offset)
     }
 
-    /// Parses [`LuauString::MultiLine`].
-    pub(crate) fn parse_multi_line(lexer: &mut Lexer) -> SmolStr {
-        let mut characters = vec!['['];
+    /// Moves the lexer after a [`LuauString::MultiLine`], which is also how
+    /// multi-line comments end.
+    pub(crate) fn skip_multi_line<I: LexerInput>(lexer: &mut Lexer<I>) {
         let start = lexer.lexer_position;
         let mut equals_count = 0;
         let mut is_done = false;
@@ -141,12 +181,9 @@ impl LuauString {
         lexer.consume('[');
         while lexer.consume('=') {
             equals_count += 1;
-            characters.push('=');
         }
 
-        if lexer.consume('[') {
-            characters.push('[');
-        } else {
+        if !lexer.consume('[') {
             lexer.errors.push(Error::new(
                 start,
                 "Missing `[`.".to_string(),
@@ -154,31 +191,38 @@ impl LuauString {
             ));
         }
 
-        while let Some(character) = lexer.current_char() {
-            characters.push(character);
-            lexer.increment_position_by_char(character);
-
-            if character == ']' && !Self::is_escaped(&characters) {
-                let mut matched_equals = true;
+        let content_start = lexer.position;
+        let mut offset = content_start;
+        let is_byte = |offset: usize, expected: u8| lexer.input.byte_at(offset) == Some(expected);
 
-                for _ in 0..equals_count {
-                    if let Some(character) = lexer.current_char() {
-                        characters.push(character);
-                        lexer.increment_position_by_char(character);
+        while let Some(found) = lexer.input.find_byte(offset, b"]") {
+            offset = found + 1;
 
-                        matched_equals = character == '=';
-                    }
-                }
+            let backslashes = (content_start..found)
+                .rev()
+                .take_while(|offset| is_byte(*offset, b'\\'))
+                .count();
+            if backslashes % 2 == 1 {
+                continue;
+            }
 
-                if matched_equals && lexer.consume(']') {
-                    characters.push(']');
-                    is_done = true;
+            let closing_end = offset + equals_count;
+            if (offset..closing_end).all(|offset| is_byte(offset, b'='))
+                && is_byte(closing_end, b']')
+            {
+                offset = closing_end + 1;
+                is_done = true;
 
-                    break;
-                }
+                break;
             }
         }
 
+        if !is_done {
+            offset = lexer.input.len();
+        }
+
+        lexer.advance_to(offset);
+
         if !is_done {
             lexer.errors.push(Error::new(
                 start,
@@ -186,18 +230,21 @@ impl LuauString {
                 Some(lexer.lexer_position),
             ));
         }
-
-        SmolStr::from_iter(characters)
     }
 }
 
 impl Lexable for LuauString {
-    fn try_lex(lexer: &mut Lexer) -> Option<Self> {
+    fn try_lex<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self> {
         match lexer.current_char()? {
             '"' => Some(Self::DoubleQuotes(Self::parse_inner(lexer, '"'))),
             '\'' => Some(Self::SingleQuotes(Self::parse_inner(lexer, '\''))),
             '`' => Some(Self::Backticks(Self::parse_inner(lexer, '`'))),
-            '[' => Some(Self::MultiLine(Self::parse_multi_line(lexer))),
+            '[' => {
+                let start = lexer.position;
+                Self::skip_multi_line(lexer);
+
+                Some(Self::MultiLine(lexer.intern_from(start)))
+            }
             _ => unreachable!("Invalid quote type."),
         }
     }
@@ -231,24 +278,22 @@ pub enum LuauNumber {
 
 impl LuauNumber {
     /// Parses a [`LuauNumber::Plain`].
-    fn parse_number_inner(lexer: &mut Lexer) -> Option<Self> {
+    fn parse_number_inner<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self> {
         let start = lexer.position;
         let mut found_decimal = false;
 
-        loop {
-            let Some(current_char) = lexer.current_char() else {
-                break;
-            };
-
+        while let Some(current_char) = lexer.current_char() {
             if is_numeric(current_char) {
                 lexer.increment_position_by_char(current_char);
             } else if current_char == '.' {
                 if found_decimal {
-                    break lexer.errors.push(Error::new(
+                    lexer.errors.push(Error::new(
                         lexer.lexer_position,
                         "Numbers can only have one decimal point.".to_string(),
                         None,
                     ));
+
+                    break;
                 }
 
                 lexer.increment_position_by_char(current_char);
@@ -258,13 +303,11 @@ impl LuauNumber {
             }
         }
 
-        Some(Self::Plain(SmolStr::from_iter(
-            lexer.chars[start..lexer.position].to_vec(),
-        )))
+        Some(Self::Plain(lexer.slice_from(start)))
     }
 
     /// Parses a [`LuauNumber::Hex`].
-    fn parse_hex_number(lexer: &mut Lexer) -> Option<Self> {
+    fn parse_hex_number<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self> {
         let start = lexer.position;
         let mut found_digit = false;
         let mut is_faulty = false;
@@ -272,16 +315,14 @@ impl LuauNumber {
         lexer.consume('0');
         lexer.consume('x');
 
-        loop {
-            let Some(current_char) = lexer.current_char() else {
-                break;
-            };
-
+        while let Some(current_char) = lexer.current_char() {
             if current_char.is_ascii_hexdigit() {
                 lexer.increment_position_by_char(current_char);
                 found_digit = true;
             } else {
-                break is_faulty = !current_char.is_whitespace();
+                is_faulty = !lexer.config.whitespace.is_whitespace(current_char);
+
+                break;
             }
         }
 
@@ -301,13 +342,11 @@ impl LuauNumber {
             ));
         }
 
-        Some(Self::Hex(SmolStr::from_iter(
-            lexer.chars[start..lexer.position].to_vec(),
-        )))
+        Some(Self::Hex(lexer.slice_from(start)))
     }
 
     /// Parses a [`LuauNumber::Binary`].
-    fn parse_binary_number(lexer: &mut Lexer) -> Option<Self> {
+    fn parse_binary_number<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self> {
         let start = lexer.position;
         let mut found_digit = false;
         let mut is_faulty = false;
@@ -315,16 +354,14 @@ impl LuauNumber {
         lexer.consume('0');
         lexer.consume('b');
 
-        loop {
-            let Some(current_char) = lexer.current_char() else {
-                break;
-            };
-
+        while let Some(current_char) = lexer.current_char() {
             if current_char == '0' || current_char == '1' {
                 lexer.increment_position_by_char(current_char);
                 found_digit = true;
             } else {
-                break is_faulty = !current_char.is_whitespace();
+                is_faulty = !lexer.config.whitespace.is_whitespace(current_char);
+
+                break;
             }
         }
 
@@ -344,14 +381,12 @@ impl LuauNumber {
             ));
         }
 
-        Some(Self::Binary(SmolStr::from_iter(
-            lexer.chars[start..lexer.position].to_vec(),
-        )))
+        Some(Self::Binary(lexer.slice_from(start)))
     }
 }
 
 impl Lexable for LuauNumber {
-    fn try_lex(lexer: &mut Lexer) -> Option<Self> {
+    fn try_lex<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self> {
         match (lexer.current_char()?, lexer.next_char()) {
             ('0', Some('b')) => Self::parse_binary_number(lexer),
             ('0', Some('x')) => Self::parse_hex_number(lexer),
@@ -377,13 +412,13 @@ pub enum Literal {
 impl Literal {
     /// Parses a [`Literal::Number`].
     #[inline]
-    pub fn parse_number(lexer: &mut Lexer) -> Option<Self> {
+    pub fn parse_number<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self> {
         LuauNumber::try_lex(lexer).map(Self::Number)
     }
 
     /// Parses a [`Literal::String`].
     #[inline]
-    pub fn parse_string(lexer: &mut Lexer) -> Option<Self> {
+    pub fn parse_string<I: LexerInput>(lexer: &mut Lexer<I>) -> Option<Self> {
         LuauString::try_lex(lexer).map(Self::String)
     }
 }
@@ -392,7 +427,7 @@ impl Lexable for Literal {
     /// This just marks literals as lexable, refrain from using it. Use
     /// [`Literal::parse_number`], or [`Literal::parse_string`], or the more
     /// specific [`LuauString::try_lex`], and [`LuauNumber::try_lex`] instead.
-    fn try_lex(_: &mut Lexer) -> Option<Self> {
+    fn try_lex<I: LexerInput>(_: &mut Lexer<I>) -> Option<Self> {
         panic!(
             "\
             `Literal::try_lex()` should never be used. \
@@ -2,7 +2,7 @@
 
 mod r#impl;
 
-use lsp_types::Position;
+use lsp_types::{Position, Range};
 use smol_str::SmolStr;
 
 use crate::prelude::{Error, PositionExt};
@@ -26,14 +26,55 @@ pub struct Token {
     /// The trivia after the token.
     pub trailing_trivia: Vec<Trivia>,
 
-    /// The ending position of this token.
+    /// The ending position of this token, this doesn't include the trailing
+    /// trivia.
     pub end: Position,
 }
 
-/// Trivia that can be before and after a token.
+/// Trivia that can be before and after a token, with where it is.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub enum Trivia {
+pub struct Trivia {
+    /// The starting position of the trivia.
+    pub start: Position,
+
+    /// The byte offset the trivia starts at.
+    pub start_offset: usize,
+
+    /// The actual trivia.
+    pub trivia_type: TriviaType,
+
+    /// The ending position of the trivia.
+    pub end: Position,
+
+    /// The byte offset the trivia ends at.
+    pub end_offset: usize,
+}
+
+impl Trivia {
+    /// Get the trivia as a string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.trivia_type.as_str()
+    }
+
+    /// Whether or not the trivia is a comment.
+    #[inline]
+    pub const fn is_comment(&self) -> bool {
+        matches!(self.trivia_type, TriviaType::Comment(_))
+    }
+}
+
+impl PartialEq<TriviaType> for Trivia {
+    fn eq(&self, other: &TriviaType) -> bool {
+        &self.trivia_type == other
+    }
+}
+
+/// All trivia types.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TriviaType {
     /// Spaces, be it whitespace, tabs, new lines, etc.
     Spaces(SmolStr),
 
@@ -41,6 +82,34 @@ pub enum Trivia {
     Comment(Comment),
 }
 
+impl TriviaType {
+    /// Get the trivia as a string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Spaces(spaces) => spaces,
+            Self::Comment(comment) => comment.as_str(),
+        }
+    }
+
+    /// Turn this trivia type into a [`Trivia`] with the passed properties.
+    pub const fn into_trivia(
+        self,
+        start: Position,
+        end: Position,
+        start_offset: usize,
+        end_offset: usize,
+    ) -> Trivia {
+        Trivia {
+            start,
+            start_offset,
+            trivia_type: self,
+            end,
+            end_offset,
+        }
+    }
+}
+
 impl Token {
     /// Creates an empty token with the specified type. This is only used when
     /// creating tokens that don't have actual positions.
@@ -54,6 +123,18 @@ impl Token {
             end: Position::MAX,
         }
     }
+
+    /// Get the range of the token, including its leading and trailing trivia.
+    pub fn full_range(&self) -> Range {
+        Range::new(
+            self.leading_trivia
+                .first()
+                .map_or(self.start, |trivia| trivia.start),
+            self.trailing_trivia
+                .last()
+                .map_or(self.end, |trivia| trivia.end),
+        )
+    }
 }
 
 impl PartialEq<TokenType> for Token {
@@ -79,6 +160,10 @@ pub enum TokenType {
     /// A comment
     Comment(Comment),
 
+    /// Spaces, only lexed as tokens with
+    /// [`LexerConfig::with_spaces_as_tokens`](crate::config::LexerConfig::with_spaces_as_tokens).
+    Spaces(SmolStr),
+
     /// A luau-reserved-keyword
     Keyword(Keyword),
 
@@ -98,6 +183,71 @@ pub enum TokenType {
     EndOfFile,
 }
 
+/// The kind of a [`TokenType`], without any of the data it holds. Literals are
+/// split by their type as they're usually treated differently.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[allow(clippy::module_name_repetitions)] // `Kind` is too general.
+pub enum TokenKind {
+    /// [`TokenType::Error`]
+    Error,
+
+    /// [`Literal::Number`]
+    Number,
+
+    /// [`Literal::String`]
+    String,
+
+    /// [`Literal::Boolean`]
+    Boolean,
+
+    /// [`TokenType::Identifier`]
+    Identifier,
+
+    /// [`TokenType::Comment`]
+    Comment,
+
+    /// [`TokenType::Keyword`]
+    Keyword,
+
+    /// [`TokenType::PartialKeyword`]
+    PartialKeyword,
+
+    /// [`TokenType::Symbol`]
+    Symbol,
+
+    /// [`TokenType::Operator`]
+    Operator,
+
+    /// [`TokenType::CompoundOperator`]
+    CompoundOperator,
+
+    /// [`TokenType::EndOfFile`]
+    EndOfFile,
+
+    /// [`TokenType::Spaces`]
+    Spaces,
+}
+
+impl TokenKind {
+    /// All token kinds, in declaration order.
+    pub const ALL: [Self; 13] = [
+        Self::Error,
+        Self::Number,
+        Self::String,
+        Self::Boolean,
+        Self::Identifier,
+        Self::Comment,
+        Self::Keyword,
+        Self::PartialKeyword,
+        Self::Symbol,
+        Self::Operator,
+        Self::CompoundOperator,
+        Self::EndOfFile,
+        Self::Spaces,
+    ];
+}
+
 impl TokenType {
     /// Turn this token type into a [`Token`] with the passed properties.
     pub const fn into_token(
@@ -118,6 +268,25 @@ impl TokenType {
 }
 
 impl TokenType {
+    /// Get the [`TokenKind`] of this token type.
+    pub const fn kind(&self) -> TokenKind {
+        match self {
+            Self::Error(_) => TokenKind::Error,
+            Self::Literal(Literal::Number(_)) => TokenKind::Number,
+            Self::Literal(Literal::String(_)) => TokenKind::String,
+            Self::Literal(Literal::Boolean(_)) => TokenKind::Boolean,
+            Self::Identifier(_) => TokenKind::Identifier,
+            Self::Comment(_) => TokenKind::Comment,
+            Self::Spaces(_) => TokenKind::Spaces,
+            Self::Keyword(_) => TokenKind::Keyword,
+            Self::PartialKeyword(_) => TokenKind::PartialKeyword,
+            Self::Symbol(_) => TokenKind::Symbol,
+            Self::Operator(_) => TokenKind::Operator,
+            Self::CompoundOperator(_) => TokenKind::CompoundOperator,
+            Self::EndOfFile => TokenKind::EndOfFile,
+        }
+    }
+
     /// Try converting this token type into a string.
     pub fn try_as_string(&self) -> Option<String> {
         match self {
@@ -136,7 +305,7 @@ impl TokenType {
                 Literal::Boolean(true) => Some("true".to_string()),
                 Literal::Boolean(false) => Some("false".to_string()),
             },
-            Self::Identifier(smol_str) => Some(smol_str.to_string()),
+            Self::Identifier(smol_str) | Self::Spaces(smol_str) => Some(smol_str.to_string()),
             Self::Comment(comment) => match comment {
                 Comment::MultiLine(smol_str) | Comment::SingleLine(smol_str) => {
                     Some(smol_str.to_string())
@@ -2,7 +2,7 @@
 
 use std::fmt::{Display, Formatter, Result};
 
-use crate::prelude::Lexer;
+use crate::prelude::{Lexer, LexerInput};
 
 /// A luau operator like `+` and `-`
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -55,24 +55,75 @@ pub enum Operator {
 
     /// `#`
     Length,
+
+    /// `&`, only in Lua 5.3 and later. Luau uses it for [`Operator::Intersection`].
+    BitwiseAnd,
+
+    /// `|`, only in Lua 5.3 and later. Luau uses it for [`Operator::Union`].
+    BitwiseOr,
+
+    /// `~`, both the binary xor and unary not, only in Lua 5.3 and later.
+    BitwiseXor,
+
+    /// `<<`, only in Lua 5.3 and later.
+    LeftShift,
+
+    /// `>>`, only in Lua 5.3 and later.
+    RightShift,
 }
 
 impl Operator {
+    /// All operators, in declaration order.
+    pub const ALL: [Self; 21] = [
+        Self::Plus,
+        Self::Minus,
+        Self::Division,
+        Self::FloorDivision,
+        Self::Multiplication,
+        Self::Modulo,
+        Self::Exponentiation,
+        Self::Concatenation,
+        Self::NotEqual,
+        Self::And,
+        Self::Or,
+        Self::Not,
+        Self::Intersection,
+        Self::Union,
+        Self::Optional,
+        Self::Length,
+        Self::BitwiseAnd,
+        Self::BitwiseOr,
+        Self::BitwiseXor,
+        Self::LeftShift,
+        Self::RightShift,
+    ];
+
     /// Try converting the character (and maybe the next) into an operator.
-    pub fn try_from_chars(character: char, lexer: &mut Lexer) -> Option<Self> {
+    pub fn try_from_chars<I: LexerInput>(character: char, lexer: &mut Lexer<I>) -> Option<Self> {
+        let dialect = lexer.dialect();
+
+        // Both characters are consumed in these cases.
+        if character == '/' && dialect.has_floor_division() && lexer.consume_with_next('/') {
+            return Some(Self::FloorDivision);
+        }
+        if character == '~' && lexer.consume_with_next('=') {
+            return Some(Self::NotEqual);
+        }
+
         let value = match character {
             '+' => Some(Self::Plus),
             '-' => Some(Self::Minus),
-            '/' if lexer.consume_with_next('/') => Some(Self::FloorDivision),
             '/' => Some(Self::Division),
             '*' => Some(Self::Multiplication),
             '%' => Some(Self::Modulo),
             '^' => Some(Self::Exponentiation),
-            '~' if lexer.consume_with_next('=') => Some(Self::NotEqual),
             '#' => Some(Self::Length),
-            '&' => Some(Self::Intersection),
-            '|' => Some(Self::Union),
-            '?' => Some(Self::Optional),
+            '&' if dialect.is_luau() => Some(Self::Intersection),
+            '|' if dialect.is_luau() => Some(Self::Union),
+            '?' if dialect.is_luau() => Some(Self::Optional),
+            '&' if dialect.has_bitwise_operators() => Some(Self::BitwiseAnd),
+            '|' if dialect.has_bitwise_operators() => Some(Self::BitwiseOr),
+            '~' if dialect.has_bitwise_operators() => Some(Self::BitwiseXor),
             _ => None,
         };
         if value.is_some() {
@@ -102,6 +153,11 @@ impl Display for Operator {
             Self::And => "and",
             Self::Or => "or",
             Self::Not => "not",
+            Self::BitwiseAnd => "&",
+            Self::BitwiseOr => "|",
+            Self::BitwiseXor => "~",
+            Self::LeftShift => "<<",
+            Self::RightShift => ">>",
         })
     }
 }
@@ -146,10 +202,29 @@ pub enum CompoundOperator {
 }
 
 impl CompoundOperator {
+    /// All compound operators, in declaration order.
+    pub const ALL: [Self; 11] = [
+        Self::PlusEqual,
+        Self::MinusEqual,
+        Self::DivisionEqual,
+        Self::FloorDivisionEqual,
+        Self::MultiplicationEqual,
+        Self::ModuloEqual,
+        Self::ExponentiationEqual,
+        Self::ConcatenationEqual,
+        Self::EqualEqual,
+        Self::LessThanOrEqualTo,
+        Self::GreaterThanOrEqualTo,
+    ];
+
     /// Try creating a compound operator from the passed operator, depending on the
     /// next character.
-    pub fn try_from_operator(operator: Operator, lexer: &mut Lexer) -> Option<Self> {
-        if !lexer.consume('=') {
+    pub fn try_from_operator<I: LexerInput>(
+        operator: Operator,
+        lexer: &mut Lexer<I>,
+    ) -> Option<Self> {
+        // Compound assignments are luau-only.
+        if !lexer.dialect().is_luau() || !lexer.consume('=') {
             return None;
         }
 
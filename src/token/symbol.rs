@@ -23,11 +23,28 @@ macro_rules! generate_symbols {
 
             /// `::`
             Typecast,
+
+            /// `::`, around labels in Lua 5.2 and later. Luau uses it for
+            /// [`Symbol::Typecast`].
+            DoubleColon,
         }
 
         impl $struct {
+            /// All items, in declaration order.
+            pub const ALL: [Self; [$(Self::$name),*].len() + 5] = [
+                $(Self::$name,)*
+                Self::Dot,
+                Self::Ellipses,
+                Self::Arrow,
+                Self::Typecast,
+                Self::DoubleColon,
+            ];
+
             /// Try creating the current item from a character
-            pub fn try_from_char(character: char, lexer: &mut crate::prelude::Lexer) -> Option<Self> {
+            pub fn try_from_char<I: crate::prelude::LexerInput>(
+                character: char,
+                lexer: &mut crate::prelude::Lexer<I>,
+            ) -> Option<Self> {
                 let value = match character {
                     $( $char => Some(Self::$name), )*
                     _ => None,
@@ -46,7 +63,7 @@ macro_rules! generate_symbols {
                     Self::Dot => f.write_str("."),
                     Self::Ellipses => f.write_str("..."),
                     Self::Arrow => f.write_str("->"),
-                    Self::Typecast => f.write_str("::"),
+                    Self::Typecast | Self::DoubleColon => f.write_str("::"),
                     $( Self::$name => f.write_char($char), )*
                 }
             }
@@ -0,0 +1,400 @@
+//! The [`TokenTree`] struct.
+
+use lsp_types::Position;
+
+use crate::prelude::{Error, Keyword, Symbol, Token, TokenType};
+
+/// Tokens nested by brackets and block keywords.
+///
+/// Groups are opened by `(`, `[`, `{`, `function`, `do`, `then`, `else`, and
+/// `repeat`, and closed by the matching `)`, `]`, `}`, `end`, or `until`.
+/// `elseif` and `else` close the group opened by `then`, with `else` opening a
+/// group of its own.
+///
+/// `then` and `else` in if expressions, like `local x = if a then b else c`,
+/// don't open groups as these expressions have no `end`.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct TokenTree<'a> {
+    /// The top-level nodes.
+    pub nodes: Vec<TokenNode<'a>>,
+
+    /// Unclosed groups, mismatched closers, and closers with nothing to close.
+    pub errors: Vec<Error>,
+}
+
+/// A single node in a [`TokenTree`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TokenNode<'a> {
+    /// A token that neither opens nor closes a group.
+    Token(&'a Token),
+
+    /// A group of tokens.
+    Group(Group<'a>),
+}
+
+/// Tokens between an opening and a closing token.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Group<'a> {
+    /// What kind of group this is.
+    pub kind: GroupKind,
+
+    /// The token that opened this group.
+    pub open: &'a Token,
+
+    /// The nodes between the opening and closing tokens.
+    pub children: Vec<TokenNode<'a>>,
+
+    /// The token that closed this group, if any.
+    pub close: Option<&'a Token>,
+
+    /// How many groups this group is in.
+    pub depth: usize,
+}
+
+/// The kind of a [`Group`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GroupKind {
+    /// `(` and `)`.
+    Parentheses,
+
+    /// `[` and `]`.
+    Brackets,
+
+    /// `{` and `}`.
+    Braces,
+
+    /// A block keyword and `end` or `until`.
+    Block,
+}
+
+impl TokenTree<'_> {
+    /// Get all groups in this tree, with each group before the groups it
+    /// contains.
+    pub fn groups(&self) -> Vec<&Group<'_>> {
+        let mut groups = Vec::new();
+        collect_groups(&self.nodes, &mut groups);
+
+        groups
+    }
+}
+
+impl<'a> TokenTree<'a> {
+    /// Build a [`TokenTree`] from the passed tokens.
+    pub fn new(tokens: &'a [Token]) -> Self {
+        let mut builder = Builder {
+            stack: vec![Frame::root()],
+            errors: Vec::new(),
+            previous: None,
+            after_if_expression_keyword: false,
+        };
+
+        for token in tokens {
+            builder.push(token);
+        }
+
+        builder.finish(tokens.last())
+    }
+}
+
+/// Push all groups in the passed nodes to `groups`.
+fn collect_groups<'a, 'b>(nodes: &'a [TokenNode<'b>], groups: &mut Vec<&'a Group<'b>>) {
+    for node in nodes {
+        if let TokenNode::Group(group) = node {
+            groups.push(group);
+            collect_groups(&group.children, groups);
+        }
+    }
+}
+
+/// A group that's still being built.
+struct Frame<'a> {
+    /// The kind and opening token of the group, [`None`] for the root.
+    open: Option<(GroupKind, &'a Token)>,
+
+    /// The nodes in this group so far.
+    children: Vec<TokenNode<'a>>,
+
+    /// `if` and `elseif` keywords waiting for their `then`, and whether they
+    /// start if expressions.
+    conditions: Vec<bool>,
+
+    /// How many if expressions are waiting for their `else`.
+    if_expressions: usize,
+}
+
+impl<'a> Frame<'a> {
+    /// Create the root frame.
+    const fn root() -> Self {
+        Self::new(None)
+    }
+
+    /// Create a new frame.
+    const fn new(open: Option<(GroupKind, &'a Token)>) -> Self {
+        Self {
+            open,
+            children: Vec::new(),
+            conditions: Vec::new(),
+            if_expressions: 0,
+        }
+    }
+}
+
+/// Builds a [`TokenTree`] one token at a time.
+struct Builder<'a> {
+    /// The groups being built, the first one being the root.
+    stack: Vec<Frame<'a>>,
+
+    /// The errors met so far.
+    errors: Vec<Error>,
+
+    /// The last token which isn't an error or a comment.
+    previous: Option<&'a Token>,
+
+    /// Whether `previous` is a `then`, `elseif`, or `else` of an if expression.
+    after_if_expression_keyword: bool,
+}
+
+impl<'a> Builder<'a> {
+    /// Get the innermost frame.
+    #[allow(clippy::missing_panics_doc)] // The root is never popped.
+    fn top(&mut self) -> &mut Frame<'a> {
+        // SAFETY: The root frame is never popped.
+        #[allow(clippy::unwrap_used)]
+        self.stack.last_mut().unwrap()
+    }
+
+    /// Add the passed token to the tree.
+    fn push(&mut self, token: &'a Token) {
+        let mut is_if_expression_keyword = false;
+
+        match &token.token_type {
+            TokenType::Symbol(Symbol::OpeningParenthesis) => {
+                self.open(GroupKind::Parentheses, token);
+            }
+            TokenType::Symbol(Symbol::OpeningBrackets) => self.open(GroupKind::Brackets, token),
+            TokenType::Symbol(Symbol::OpeningCurlyBrackets) => self.open(GroupKind::Braces, token),
+            TokenType::Symbol(
+                Symbol::ClosingParenthesis | Symbol::ClosingBrackets | Symbol::ClosingCurlyBrackets,
+            )
+            | TokenType::Keyword(Keyword::End | Keyword::Until) => {
+                self.close(token);
+            }
+            TokenType::Keyword(Keyword::Function | Keyword::Do | Keyword::Repeat) => {
+                self.open(GroupKind::Block, token);
+            }
+            TokenType::Keyword(Keyword::If) => {
+                let is_expression = self.expects_expression();
+                let frame = self.top();
+
+                frame.conditions.push(is_expression);
+                if is_expression {
+                    frame.if_expressions += 1;
+                }
+                frame.children.push(TokenNode::Token(token));
+            }
+            TokenType::Keyword(Keyword::Then) => {
+                let frame = self.top();
+
+                if frame.conditions.pop().unwrap_or_default() {
+                    is_if_expression_keyword = true;
+                    frame.children.push(TokenNode::Token(token));
+                } else {
+                    self.open(GroupKind::Block, token);
+                }
+            }
+            TokenType::Keyword(Keyword::Elseif) => {
+                if self.top().if_expressions > 0 {
+                    is_if_expression_keyword = true;
+                    self.top().conditions.push(true);
+                    self.top().children.push(TokenNode::Token(token));
+                } else {
+                    self.close(token);
+                    self.top().conditions.push(false);
+                }
+            }
+            TokenType::Keyword(Keyword::Else) => {
+                if self.top().if_expressions > 0 {
+                    is_if_expression_keyword = true;
+                    self.top().if_expressions -= 1;
+                    self.top().children.push(TokenNode::Token(token));
+                } else if self.close(token) {
+                    self.open(GroupKind::Block, token);
+                }
+            }
+            _ => self.top().children.push(TokenNode::Token(token)),
+        }
+
+        if !matches!(
+            token.token_type,
+            TokenType::Error(_) | TokenType::Comment(_) | TokenType::Spaces(_)
+        ) {
+            self.previous = Some(token);
+            self.after_if_expression_keyword = is_if_expression_keyword;
+        }
+    }
+
+    /// Whether or not the previous token is followed by an expression, meaning
+    /// an `if` after it starts an if expression.
+    fn expects_expression(&self) -> bool {
+        self.after_if_expression_keyword
+            || self.previous.is_some_and(|token| match &token.token_type {
+                TokenType::Operator(_) | TokenType::CompoundOperator(_) => true,
+                TokenType::Symbol(symbol) => matches!(
+                    symbol,
+                    Symbol::Equal
+                        | Symbol::Comma
+                        | Symbol::OpeningParenthesis
+                        | Symbol::OpeningBrackets
+                        | Symbol::OpeningCurlyBrackets
+                ),
+                TokenType::Keyword(keyword) => matches!(
+                    keyword,
+                    Keyword::Return
+                        | Keyword::In
+                        | Keyword::Until
+                        | Keyword::While
+                        | Keyword::If
+                        | Keyword::Elseif
+                ),
+                _ => false,
+            })
+    }
+
+    /// Open a new group.
+    fn open(&mut self, kind: GroupKind, token: &'a Token) {
+        self.stack.push(Frame::new(Some((kind, token))));
+    }
+
+    /// Close the innermost group the passed token can close, and return whether
+    /// there was one. Groups inside it are reported as unclosed.
+    fn close(&mut self, token: &'a Token) -> bool {
+        let Some(index) = self.stack.iter().rposition(|frame| {
+            frame
+                .open
+                .is_some_and(|(_, open)| closes(&open.token_type, &token.token_type))
+        }) else {
+            self.errors.push(Error::new(
+                token.start,
+                format!("Unexpected `{}` with nothing to close.", text(token)),
+                Some(token.end),
+            ));
+            self.top().children.push(TokenNode::Token(token));
+
+            return false;
+        };
+
+        while self.stack.len() > index + 1 {
+            self.pop(None, token.end, |open| {
+                format!(
+                    "Expected {} to close `{}`, found `{}`.",
+                    expected_closer(open),
+                    text(open),
+                    text(token)
+                )
+            });
+        }
+
+        self.pop(Some(token), token.end, |_| String::new());
+
+        true
+    }
+
+    /// Pop the innermost group, reporting it from its opening token to `end`
+    /// with the passed message if it isn't closed.
+    fn pop(
+        &mut self,
+        close: Option<&'a Token>,
+        end: Position,
+        message: impl FnOnce(&Token) -> String,
+    ) {
+        // SAFETY: Only called when there's more than the root frame.
+        #[allow(clippy::unwrap_used)]
+        let frame = self.stack.pop().unwrap();
+        let Some((kind, open)) = frame.open else {
+            return;
+        };
+
+        if close.is_none() {
+            self.errors
+                .push(Error::new(open.start, message(open), Some(end)));
+        }
+
+        let depth = self.stack.len() - 1;
+        self.top().children.push(TokenNode::Group(Group {
+            kind,
+            open,
+            children: frame.children,
+            close,
+            depth,
+        }));
+    }
+
+    /// Close all remaining groups and return the tree.
+    fn finish(mut self, last: Option<&'a Token>) -> TokenTree<'a> {
+        // Keep the end of file token at the root.
+        let end_of_file = self.top().children.pop_if(
+            |node| matches!(node, TokenNode::Token(token) if **token == TokenType::EndOfFile),
+        );
+        let end = last.map_or_else(Position::default, |last| last.end);
+
+        while self.stack.len() > 1 {
+            self.pop(None, end, |open| {
+                format!(
+                    "Missing {} to close `{}`.",
+                    expected_closer(open),
+                    text(open)
+                )
+            });
+        }
+
+        let mut root = self.stack.remove(0);
+        root.children.extend(end_of_file);
+
+        TokenTree {
+            nodes: root.children,
+            errors: self.errors,
+        }
+    }
+}
+
+/// Whether or not a token of type `close` closes a group opened by `open`.
+const fn closes(open: &TokenType, close: &TokenType) -> bool {
+    match (open, close) {
+        (TokenType::Symbol(open), TokenType::Symbol(close)) => matches!(
+            (open, close),
+            (Symbol::OpeningParenthesis, Symbol::ClosingParenthesis)
+                | (Symbol::OpeningBrackets, Symbol::ClosingBrackets)
+                | (Symbol::OpeningCurlyBrackets, Symbol::ClosingCurlyBrackets)
+        ),
+        (TokenType::Keyword(open), TokenType::Keyword(close)) => matches!(
+            (open, close),
+            (
+                Keyword::Function | Keyword::Do | Keyword::Then | Keyword::Else,
+                Keyword::End
+            ) | (Keyword::Repeat, Keyword::Until)
+                | (Keyword::Then, Keyword::Elseif | Keyword::Else)
+        ),
+        _ => false,
+    }
+}
+
+/// The closer expected by the passed opening token, for error messages.
+const fn expected_closer(open: &Token) -> &'static str {
+    match open.token_type {
+        TokenType::Symbol(Symbol::OpeningParenthesis) => "`)`",
+        TokenType::Symbol(Symbol::OpeningBrackets) => "`]`",
+        TokenType::Symbol(Symbol::OpeningCurlyBrackets) => "`}`",
+        TokenType::Keyword(Keyword::Repeat) => "`until`",
+        _ => "`end`",
+    }
+}
+
+/// The text of the passed token, for error messages.
+fn text(token: &Token) -> String {
+    token
+        .token_type
+        .try_as_string()
+        .unwrap_or_else(|| "<eof>".to_string())
+}
@@ -1,19 +1,101 @@
 //! Helper functions for the lexer.
 
+use lsp_types::Position;
+
+/// The class of ascii letters and `_`.
+const IDENTIFIER_START: u8 = 1;
+
+/// The class of ascii letters, digits, and `_`.
+const IDENTIFIER: u8 = 1 << 1;
+
+/// The class of ascii digits and `_`.
+const NUMERIC: u8 = 1 << 2;
+
+/// The class of ascii whitespace, as in [`char::is_whitespace`].
+const WHITESPACE: u8 = 1 << 3;
+
+/// The classes of each ascii byte, so checking them is a single lookup.
+const CLASSES: [u8; 128] = {
+    let mut classes = [0; 128];
+    let mut byte = 0;
+
+    while byte < 128 {
+        let character = byte as u8;
+
+        if character.is_ascii_alphabetic() || character == b'_' {
+            classes[byte] |= IDENTIFIER_START | IDENTIFIER;
+        }
+        if character.is_ascii_digit() || character == b'_' {
+            classes[byte] |= IDENTIFIER | NUMERIC;
+        }
+        if matches!(character, b' ' | b'\t' | b'\n' | b'\r' | b'\x0B' | b'\x0C') {
+            classes[byte] |= WHITESPACE;
+        }
+
+        byte += 1;
+    }
+
+    classes
+};
+
+/// Whether or not the passed byte is ascii and in the passed class.
+#[inline]
+const fn is_in_class(byte: u8, class: u8) -> bool {
+    byte < 128 && CLASSES[byte as usize] & class != 0
+}
+
+/// Whether or not the passed character is ascii and in the passed class.
+#[inline]
+const fn is_char_in_class(character: char, class: u8) -> bool {
+    character.is_ascii() && is_in_class(character as u8, class)
+}
+
 /// Whether or not this character is valid to be in a number.
 #[inline]
 pub const fn is_numeric(character: char) -> bool {
-    matches!(character, '0'..='9' | '_')
+    is_char_in_class(character, NUMERIC)
 }
 
 /// Whether or not this character is a valid identifier start.
 #[inline]
 pub const fn is_identifier_start(character: char) -> bool {
-    matches!(character, 'a'..='z' | 'A'..='Z' | '_')
+    is_char_in_class(character, IDENTIFIER_START)
 }
 
 /// Whether or not this character is valid to be in an identifier.
 #[inline]
 pub const fn can_be_identifier(character: char) -> bool {
-    is_identifier_start(character) | is_numeric(character)
+    is_char_in_class(character, IDENTIFIER)
+}
+
+/// Whether or not this byte is valid to be in an identifier. Identifiers are
+/// ascii-only so this never splits a character.
+#[inline]
+pub const fn can_be_identifier_byte(byte: u8) -> bool {
+    is_in_class(byte, IDENTIFIER)
+}
+
+/// Whether or not this byte is ascii whitespace.
+#[inline]
+pub const fn is_ascii_whitespace(byte: u8) -> bool {
+    is_in_class(byte, WHITESPACE)
+}
+
+/// Get the position after the passed text if it starts at the passed position,
+/// with tabs taking `tab_width` columns.
+pub fn advance_position(mut position: Position, text: &str, tab_width: u32) -> Position {
+    for byte in text.bytes() {
+        match byte {
+            b'\n' => {
+                position.line += 1;
+                position.character = 0;
+            }
+            b'\t' => position.character += tab_width,
+            // Continuation bytes are part of the previous character.
+            0x80..=0xBF => (),
+            _ => position.character += 1,
+        }
+    }
+
+    position
 }
@@ -0,0 +1,140 @@
+//! The [`LuauVersion`] and [`LuauFeature`] enums and the [`LuauFeatures`] set.
+
+use std::fmt::{Display, Formatter, Result};
+
+/// The Luau releases which added syntax, from oldest to newest. Each release
+/// supports the syntax of the ones before it, so a runtime is targeted with the
+/// release which added the newest syntax it can run.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub enum LuauVersion {
+    /// Releases before string interpolation, which only have compound
+    /// assignments.
+    CompoundAssignment,
+
+    /// The release which added string interpolation.
+    StringInterpolation,
+
+    /// The release which added floor division.
+    FloorDivision,
+
+    /// The release which added attributes.
+    Attributes,
+
+    /// The release which added type functions, this is the default.
+    #[default]
+    TypeFunctions,
+}
+
+impl LuauVersion {
+    /// The newest release.
+    pub const LATEST: Self = Self::TypeFunctions;
+
+    /// Get the [`LuauFeatures`] supported by this release.
+    pub const fn features(self) -> LuauFeatures {
+        match self {
+            Self::CompoundAssignment => LuauFeatures::NONE.with(LuauFeature::CompoundAssignment),
+            Self::StringInterpolation => Self::CompoundAssignment
+                .features()
+                .with(LuauFeature::StringInterpolation),
+            Self::FloorDivision => Self::StringInterpolation
+                .features()
+                .with(LuauFeature::FloorDivision),
+            Self::Attributes => Self::FloorDivision.features().with(LuauFeature::Attributes),
+            Self::TypeFunctions => Self::Attributes.features().with(LuauFeature::TypeFunctions),
+        }
+    }
+}
+
+/// Syntax which was added to Luau over time. Older runtimes can't run code
+/// using it.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LuauFeature {
+    /// Compound assignments like `+=` and `..=`.
+    CompoundAssignment = 1 << 0,
+
+    /// Backtick strings, as in `` `Hello, {name}!` ``.
+    StringInterpolation = 1 << 1,
+
+    /// The `//` operator and `//=`.
+    FloorDivision = 1 << 2,
+
+    /// Attributes like `@native`.
+    Attributes = 1 << 3,
+
+    /// User-defined type functions, as in `type function foo() end`.
+    TypeFunctions = 1 << 4,
+}
+
+impl Display for LuauFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            Self::CompoundAssignment => "compound assignments",
+            Self::StringInterpolation => "string interpolation",
+            Self::FloorDivision => "floor division",
+            Self::Attributes => "attributes",
+            Self::TypeFunctions => "type functions",
+        })
+    }
+}
+
+/// A set of [`LuauFeature`]s supported by the targeted Luau runtime. Tokens
+/// using features outside of this set are reported by the lexer.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LuauFeatures(u8);
+
+impl LuauFeatures {
+    /// All features, this is the default.
+    pub const ALL: Self = Self::NONE
+        .with(LuauFeature::CompoundAssignment)
+        .with(LuauFeature::StringInterpolation)
+        .with(LuauFeature::FloorDivision)
+        .with(LuauFeature::Attributes)
+        .with(LuauFeature::TypeFunctions);
+
+    /// No features.
+    pub const NONE: Self = Self(0);
+
+    /// Add the passed feature to the set. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with(self, feature: LuauFeature) -> Self {
+        Self(self.0 | feature as u8)
+    }
+
+    /// Remove the passed feature from the set. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn without(self, feature: LuauFeature) -> Self {
+        Self(self.0 & !(feature as u8))
+    }
+
+    /// Whether or not the set has the passed feature.
+    #[inline]
+    pub const fn contains(self, feature: LuauFeature) -> bool {
+        self.0 & feature as u8 != 0
+    }
+}
+
+impl From<LuauVersion> for LuauFeatures {
+    #[inline]
+    fn from(version: LuauVersion) -> Self {
+        version.features()
+    }
+}
+
+impl Default for LuauFeatures {
+    #[inline]
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl FromIterator<LuauFeature> for LuauFeatures {
+    fn from_iter<T: IntoIterator<Item = LuauFeature>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::NONE, Self::with)
+    }
+}
@@ -0,0 +1,274 @@
+//! The [`WorkspaceLexer`] struct, to lex many files in parallel.
+
+use std::{
+    fs, io,
+    ops::ControlFlow,
+    panic,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::prelude::{Error, Lexer, LexerConfig, Token, TokenType};
+
+/// A file to lex with a [`WorkspaceLexer`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub enum WorkspaceFile {
+    /// A file on disk, read by the thread lexing it.
+    Path(PathBuf),
+
+    /// Code which was already read, and the id to report it with.
+    Code {
+        /// The id of the file, like its path or URI.
+        id: String,
+
+        /// The code of the file.
+        code: String,
+    },
+}
+
+impl WorkspaceFile {
+    /// Get the id of the file, which is the path for files on disk.
+    pub fn id(&self) -> String {
+        match self {
+            Self::Path(path) => path.display().to_string(),
+            Self::Code { id, .. } => id.clone(),
+        }
+    }
+}
+
+impl From<PathBuf> for WorkspaceFile {
+    #[inline]
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&Path> for WorkspaceFile {
+    #[inline]
+    fn from(path: &Path) -> Self {
+        Self::Path(path.to_path_buf())
+    }
+}
+
+impl From<(String, String)> for WorkspaceFile {
+    #[inline]
+    fn from((id, code): (String, String)) -> Self {
+        Self::Code { id, code }
+    }
+}
+
+/// The result of lexing a single [`WorkspaceFile`].
+#[derive(Debug)]
+pub struct LexedFile {
+    /// The id of the file, see [`WorkspaceFile::id`].
+    pub id: String,
+
+    /// All tokens of the file, the last one being [`TokenType::EndOfFile`].
+    /// Empty if the file couldn't be read.
+    pub tokens: Vec<Token>,
+
+    /// The errors and warnings met while lexing, in the order of their tokens.
+    pub errors: Vec<Error>,
+
+    /// The error met while reading the file from disk, if any.
+    pub read_error: Option<io::Error>,
+
+    /// How long reading and lexing the file took.
+    pub duration: Duration,
+}
+
+/// The result of [`WorkspaceLexer::lex`].
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct LexedWorkspace {
+    /// The lexed files, in the same order as they were passed. Files which
+    /// weren't lexed because of a cancellation are left out.
+    pub files: Vec<LexedFile>,
+
+    /// Whether or not lexing was cancelled by the progress callback.
+    pub is_cancelled: bool,
+
+    /// How long lexing all files took.
+    pub duration: Duration,
+}
+
+/// The progress of a [`WorkspaceLexer`], passed to the callback of
+/// [`WorkspaceLexer::lex_with_progress`] after each file.
+#[derive(Clone, Copy, Debug)]
+pub struct Progress<'a> {
+    /// The file which was just lexed.
+    pub file: &'a LexedFile,
+
+    /// The index of the file in the passed ones.
+    pub index: usize,
+
+    /// The number of files lexed so far, including this one.
+    pub completed: usize,
+
+    /// The total number of files.
+    pub total: usize,
+}
+
+/// Lexes many files across a pool of threads, like all files of a workspace.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)] // Everything is in the prelude.
+pub struct WorkspaceLexer {
+    /// The configuration of the lexers.
+    config: LexerConfig,
+
+    /// The number of threads to use, `0` for the available parallelism.
+    threads: usize,
+}
+
+impl WorkspaceLexer {
+    /// Create a new [`WorkspaceLexer`] using all available threads.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            config: LexerConfig::new(),
+            threads: 0,
+        }
+    }
+
+    /// Set the [`LexerConfig`] all files are lexed with. Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_config(mut self, config: LexerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the number of threads to use, `0` being the available parallelism.
+    /// Meant to be chained.
+    #[inline]
+    #[must_use]
+    pub const fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Get the number of threads to use for the passed number of files.
+    fn threads_for(&self, files: usize) -> usize {
+        let threads = if self.threads == 0 {
+            thread::available_parallelism().map_or(1, usize::from)
+        } else {
+            self.threads
+        };
+
+        threads.min(files).max(1)
+    }
+
+    /// Lex all passed files.
+    #[inline]
+    pub fn lex(&self, files: &[WorkspaceFile]) -> LexedWorkspace {
+        self.lex_with_progress(files, |_| ControlFlow::Continue(()))
+    }
+
+    /// Lex all passed files, calling `progress` after each one from the thread
+    /// which lexed it. Returning [`ControlFlow::Break`] from it cancels lexing,
+    /// files which are being lexed at that point are still finished.
+    pub fn lex_with_progress(
+        &self,
+        files: &[WorkspaceFile],
+        progress: impl Fn(Progress) -> ControlFlow<()> + Sync,
+    ) -> LexedWorkspace {
+        let start = Instant::now();
+        let next = AtomicUsize::new(0);
+        let completed = AtomicUsize::new(0);
+        let is_cancelled = AtomicBool::new(false);
+
+        let worker = || {
+            let mut lexer = self.config.build("");
+            let mut lexed = Vec::new();
+
+            while !is_cancelled.load(Ordering::Relaxed) {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(file) = files.get(index) else {
+                    break;
+                };
+
+                let file = lex_file(&mut lexer, file);
+                let flow = progress(Progress {
+                    file: &file,
+                    index,
+                    completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                    total: files.len(),
+                });
+                if flow.is_break() {
+                    is_cancelled.store(true, Ordering::Relaxed);
+                }
+
+                lexed.push((index, file));
+            }
+
+            lexed
+        };
+
+        let mut lexed = Vec::new();
+        lexed.resize_with(files.len(), || None);
+
+        thread::scope(|scope| {
+            let workers = (0..self.threads_for(files.len()))
+                .map(|_| scope.spawn(worker))
+                .collect::<Vec<_>>();
+
+            for worker in workers {
+                match worker.join() {
+                    Ok(files) => {
+                        for (index, file) in files {
+                            lexed[index] = Some(file);
+                        }
+                    }
+                    Err(payload) => panic::resume_unwind(payload),
+                }
+            }
+        });
+
+        LexedWorkspace {
+            files: lexed.into_iter().flatten().collect(),
+            is_cancelled: is_cancelled.into_inner(),
+            duration: start.elapsed(),
+        }
+    }
+}
+
+/// Read and lex the passed file, reusing the passed lexer.
+fn lex_file(lexer: &mut Lexer, file: &WorkspaceFile) -> LexedFile {
+    let start = Instant::now();
+
+    match file {
+        WorkspaceFile::Path(path) => match fs::read_to_string(path) {
+            Ok(code) => lexer.reset(&code),
+            Err(error) => {
+                return LexedFile {
+                    id: file.id(),
+                    tokens: Vec::new(),
+                    errors: Vec::new(),
+                    read_error: Some(error),
+                    duration: start.elapsed(),
+                };
+            }
+        },
+        WorkspaceFile::Code { code, .. } => lexer.reset(code),
+    }
+
+    let tokens = lexer.collect_tokens();
+    let errors = tokens
+        .iter()
+        .filter_map(|token| match &token.token_type {
+            TokenType::Error(error) => Some(error.clone()),
+            _ => None,
+        })
+        .collect();
+
+    LexedFile {
+        id: file.id(),
+        tokens,
+        errors,
+        read_error: None,
+        duration: start.elapsed(),
+    }
+}
@@ -0,0 +1,122 @@
+use luau_lexer::prelude::{
+    CACHE_VERSION, CacheError, CompoundOperator, Dialect, Keyword, Lexer, LexerConfig, MAGIC,
+    Operator, PartialKeyword, Symbol, TokenCache,
+};
+
+const CODE: &str = r#"--!strict
+local héllo: number? = 0x1F + 0b10 // 2 -- note
+--[==[ multi
+line ]==]
+type T = { [string]: boolean }
+	héllo ..= `{true}` .. [[raw]] .. 'a' .. "b"
+local bad = 'unterminated
+return héllo ~= nil and not false, ...
+"#;
+
+/// Write the passed cache to a buffer.
+fn write(cache: &TokenCache) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    cache.write_to(&mut bytes).unwrap();
+
+    bytes
+}
+
+#[test]
+fn round_trip() {
+    let cache = TokenCache::new(CODE);
+    let bytes = write(&cache);
+    let read = TokenCache::read_from(bytes.as_slice(), CODE, &LexerConfig::new()).unwrap();
+
+    assert_eq!(read, cache);
+    assert_eq!(read.into_tokens(), Lexer::new(CODE).collect_tokens());
+}
+
+#[test]
+fn round_trip_with_config() {
+    let config = LexerConfig::new()
+        .with_dialect(Dialect::Lua54)
+        .with_tab_width(4)
+        .with_comments_as_tokens(true);
+    let code = "local x <const> = 1 & 2 -- bits\n\tgoto done ::done::";
+    let cache = TokenCache::from_lexer(&mut config.build(code));
+    let bytes = write(&cache);
+
+    let read = TokenCache::read_from(bytes.as_slice(), code, &config).unwrap();
+    assert_eq!(read.tokens(), config.build(code).collect_tokens());
+}
+
+#[test]
+fn stale() {
+    let bytes = write(&TokenCache::new(CODE));
+    let config = LexerConfig::new();
+
+    assert!(TokenCache::new(CODE).is_fresh(CODE, &config));
+    assert!(matches!(
+        TokenCache::read_from(bytes.as_slice(), "local x = 1", &config),
+        Err(CacheError::Stale)
+    ));
+    assert!(matches!(
+        TokenCache::read_from(bytes.as_slice(), CODE, &config.with_tab_width(4)),
+        Err(CacheError::Stale)
+    ));
+}
+
+#[test]
+fn corrupted() {
+    let bytes = write(&TokenCache::new(CODE));
+    let config = LexerConfig::new();
+    let read = |bytes: &[u8]| TokenCache::read_from(bytes, CODE, &config);
+
+    assert!(matches!(read(b""), Err(CacheError::NotACache)));
+    assert!(matches!(read(b"local x = 1"), Err(CacheError::NotACache)));
+    let next_version = CACHE_VERSION + 1;
+    assert!(matches!(
+        read(&[&MAGIC[..], &[next_version as u8]].concat()),
+        Err(CacheError::UnsupportedVersion(version)) if version == next_version
+    ));
+
+    let mut other_version = bytes.clone();
+    let version = env!("CARGO_PKG_VERSION");
+    let start = other_version
+        .windows(version.len())
+        .position(|window| window == version.as_bytes())
+        .unwrap();
+    other_version[start] = b'9';
+    assert!(matches!(
+        read(&other_version),
+        Err(CacheError::CrateVersion(version)) if version.starts_with('9')
+    ));
+
+    for length in [bytes.len() / 2, bytes.len() - 1] {
+        assert!(matches!(read(&bytes[..length]), Err(CacheError::Corrupted)));
+    }
+
+    let mut flipped = bytes.clone();
+    let last = flipped.len() - 10;
+    flipped[last] ^= 0x40;
+    assert!(matches!(read(&flipped), Err(CacheError::Corrupted)));
+
+    let mut extended = bytes;
+    extended.push(0);
+    assert!(matches!(read(&extended), Err(CacheError::Corrupted)));
+}
+
+#[test]
+fn item_indices() {
+    // Items are stored as their index in `ALL`.
+    for (index, item) in Keyword::ALL.into_iter().enumerate() {
+        assert_eq!(item as usize, index);
+    }
+    for (index, item) in PartialKeyword::ALL.into_iter().enumerate() {
+        assert_eq!(item as usize, index);
+    }
+    for (index, item) in Symbol::ALL.into_iter().enumerate() {
+        assert_eq!(item as usize, index);
+    }
+    for (index, item) in Operator::ALL.into_iter().enumerate() {
+        assert_eq!(item as usize, index);
+    }
+    for (index, item) in CompoundOperator::ALL.into_iter().enumerate() {
+        assert_eq!(item as usize, index);
+    }
+}
@@ -0,0 +1,84 @@
+use luau_lexer::prelude::{Lexer, LexerConfig, Symbol, Token, TokenType};
+
+/// Lex the next `count` tokens.
+fn lex(lexer: &mut Lexer, count: usize) -> Vec<Token> {
+    (0..count).map(|_| lexer.next_token()).collect()
+}
+
+#[test]
+fn rewind() {
+    let mut lexer = Lexer::new("local x: Map<string, --[[ comment ]] number> = {}");
+    lex(&mut lexer, 3);
+
+    let checkpoint = lexer.checkpoint();
+    let tokens = lexer.collect_tokens();
+
+    lexer.rewind(checkpoint);
+    assert_eq!(lexer.collect_tokens(), tokens);
+}
+
+#[test]
+fn rewind_without_moving() {
+    let mut lexer = Lexer::new("a -- comment\nb");
+    let checkpoint = lexer.checkpoint();
+    let tokens = lexer.collect_tokens();
+
+    lexer.rewind(checkpoint);
+    lexer.rewind(checkpoint);
+    assert_eq!(lexer.collect_tokens(), tokens);
+}
+
+#[test]
+fn rewind_errors() {
+    let mut lexer = LexerConfig::new()
+        .with_max_errors(Some(3))
+        .build("a = 'unterminated\nb = 'again\nc = 1");
+    lex(&mut lexer, 3);
+
+    // The string's errors are still pending.
+    let checkpoint = lexer.checkpoint();
+    let tokens = lexer.collect_tokens();
+
+    lexer.rewind(checkpoint);
+    assert_eq!(lexer.collect_tokens(), tokens);
+}
+
+#[test]
+fn try_speculate() {
+    let mut lexer = Lexer::new("a < b > c");
+    lexer.next_token();
+
+    let result = lexer.try_speculate(|lexer| {
+        lexer.next_token();
+        lexer.next_token();
+
+        match lexer.next_token().token_type {
+            TokenType::Symbol(Symbol::OpeningParenthesis) => Ok(()),
+            _ => Err(()),
+        }
+    });
+    assert_eq!(result, Err(()));
+    assert_eq!(
+        lexer.next_token().token_type,
+        TokenType::Symbol(Symbol::OpeningAngleBrackets)
+    );
+
+    let result = lexer.try_speculate(|lexer| Ok::<_, ()>(lexer.next_token()));
+    assert!(result.is_ok_and(|token| token.token_type == TokenType::Identifier("b".into())));
+    assert_eq!(
+        lexer.next_token().token_type,
+        TokenType::Symbol(Symbol::ClosingAngleBrackets)
+    );
+}
+
+#[test]
+fn rewind_at_end() {
+    let mut lexer = Lexer::new("a -- comment");
+    lexer.next_token();
+
+    let checkpoint = lexer.checkpoint();
+    let tokens = lexer.collect_tokens();
+
+    lexer.rewind(checkpoint);
+    assert_eq!(lexer.collect_tokens(), tokens);
+}
@@ -0,0 +1,80 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// Create a directory with the passed files, unique to the calling test.
+fn directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let directory = env::temp_dir().join(format!("luau-lex-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+
+    for (path, code) in files {
+        let path = directory.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, code).unwrap();
+    }
+
+    directory
+}
+
+/// Run `luau-lex` with the passed arguments.
+fn run(arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_luau-lex"))
+        .args(arguments)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn table() {
+    let directory = directory("table", &[("main.luau", "local x = 1")]);
+    let output = run(&[directory.join("main.luau").to_str().unwrap()]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("1:1-1:6    Keyword     local"), "{stdout}");
+    assert!(!stdout.contains("Whitespace"));
+}
+
+#[test]
+fn directories_and_globs() {
+    let directory = directory(
+        "directories",
+        &[
+            ("a.luau", "local a"),
+            ("nested/b.lua", "local b"),
+            ("nested/c.txt", "not lua"),
+        ],
+    );
+    let output = run(&["-f", "json", directory.to_str().unwrap()]);
+    let files: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(files.as_array().unwrap().len(), 2);
+    assert_eq!(files[0]["schema_version"], 1);
+    assert_eq!(files[0]["tokens"][0]["kind"], "keyword");
+
+    let pattern = directory.join("**").join("*.lua");
+    let output = run(&["-f", "jsonl", "--trivia", pattern.to_str().unwrap()]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(stdout.lines().count(), 3);
+    assert!(stdout.contains("\"schema_version\":1"));
+    assert!(stdout.contains("leading_trivia\":[{\"kind\":\"whitespace\""));
+}
+
+#[test]
+fn errors() {
+    let directory = directory("errors", &[("a.luau", "local a = 'oops\n")]);
+    let output = run(&["--errors", directory.to_str().unwrap()]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("Missing ' to close string."));
+    assert!(!stdout.contains("Keyword"));
+
+    let output = run(&[directory.join("missing").to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+}
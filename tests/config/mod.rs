@@ -0,0 +1,102 @@
+use luau_lexer::prelude::{
+    Comment, Identifiers, LexerConfig, Symbol, TokenType, TriviaType, Whitespace,
+};
+
+#[test]
+fn whitespace() {
+    let code = "a\u{a0}b";
+
+    let tokens = LexerConfig::new().build(code).collect_tokens();
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(
+        tokens[0].trailing_trivia,
+        [TriviaType::Spaces("\u{a0}".into())]
+    );
+
+    let tokens = LexerConfig::new()
+        .with_whitespace(Whitespace::Ascii)
+        .build(code)
+        .collect_tokens();
+    assert!(matches!(tokens[1].token_type, TokenType::Error(_)));
+}
+
+#[test]
+fn identifiers() {
+    let code = "local café = 1";
+
+    let tokens = LexerConfig::new().build(code).collect_tokens();
+    assert_eq!(tokens[1], TokenType::Identifier("caf".into()));
+    assert!(matches!(tokens[2].token_type, TokenType::Error(_)));
+
+    let tokens = LexerConfig::new()
+        .with_identifiers(Identifiers::Unicode)
+        .build(code)
+        .collect_tokens();
+    assert_eq!(tokens.len(), 5);
+    assert_eq!(tokens[1], TokenType::Identifier("café".into()));
+    assert_eq!((tokens[1].start.line, tokens[1].start.character), (0, 6));
+    assert_eq!((tokens[2].start.line, tokens[2].start.character), (0, 11));
+}
+
+#[test]
+fn tab_width() {
+    let code = "\t\tfoo\n\tbar";
+    let tokens = LexerConfig::new()
+        .with_tab_width(4)
+        .build(code)
+        .collect_tokens();
+
+    assert_eq!((tokens[0].start.line, tokens[0].start.character), (0, 8));
+    assert_eq!((tokens[1].start.line, tokens[1].start.character), (1, 4));
+    assert_eq!((tokens[1].end.line, tokens[1].end.character), (1, 7));
+
+    let tokens = LexerConfig::new().build(code).collect_tokens();
+    assert_eq!(tokens[0].start.character, 2);
+}
+
+#[test]
+fn max_errors() {
+    let code = "$ $ $ $ local\nx";
+    let tokens = LexerConfig::new()
+        .with_max_errors(Some(2))
+        .build(code)
+        .collect_tokens();
+
+    let TokenType::Error(error) = &tokens[2].token_type else {
+        panic!("expected an error, got {:?}", tokens[2]);
+    };
+    assert_eq!(
+        error.message(),
+        "Stopped lexing after reaching the maximum of 2 errors."
+    );
+    assert_eq!(tokens.len(), 4);
+    assert_eq!(tokens[3], TokenType::EndOfFile);
+    assert_eq!((tokens[3].start.line, tokens[3].start.character), (1, 1));
+
+    let tokens = LexerConfig::new().build(code).collect_tokens();
+    assert_eq!(tokens.len(), 7);
+}
+
+#[test]
+fn comments_as_tokens() {
+    let code = "-- a\n(--[[ b ]])";
+    let tokens = LexerConfig::new()
+        .with_comments_as_tokens(true)
+        .build(code)
+        .collect_tokens();
+
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect::<Vec<_>>(),
+        [
+            TokenType::Comment(Comment::SingleLine("-- a".into())),
+            TokenType::Symbol(Symbol::OpeningParenthesis),
+            TokenType::Comment(Comment::MultiLine("--[[ b ]]".into())),
+            TokenType::Symbol(Symbol::ClosingParenthesis),
+            TokenType::EndOfFile,
+        ]
+    );
+    assert_eq!(tokens[1].leading_trivia, [TriviaType::Spaces("\n".into())]);
+}
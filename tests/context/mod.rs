@@ -0,0 +1,103 @@
+use lsp_types::Position;
+use luau_lexer::prelude::CursorContext;
+
+/// Get the context at the `|` in the passed code.
+fn context(code: &str) -> CursorContext {
+    let (before, after) = code.split_once('|').unwrap();
+    let line = before.matches('\n').count() as u32;
+    let character = before.rsplit('\n').next().unwrap().chars().count() as u32;
+
+    CursorContext::at(&format!("{before}{after}"), Position::new(line, character))
+}
+
+#[test]
+fn comments() {
+    assert_eq!(context("-- hel|lo"), CursorContext::Comment);
+    assert_eq!(context("local x -- end|\n"), CursorContext::Comment);
+    assert_eq!(context("--[[ a\n b| ]]"), CursorContext::Comment);
+    assert_eq!(context("--[[ a ]]|"), CursorContext::Code);
+    assert_eq!(context("|-- a"), CursorContext::Code);
+}
+
+#[test]
+fn strings() {
+    assert_eq!(context("print('he|llo')"), CursorContext::String);
+    assert_eq!(context("print('hello'|)"), CursorContext::Code);
+    assert_eq!(context("print('hel|"), CursorContext::UnterminatedString);
+    assert_eq!(
+        context("print('hel|\nlocal x = 1"),
+        CursorContext::UnterminatedString
+    );
+    assert_eq!(
+        context("local s = [[\nab|"),
+        CursorContext::UnterminatedString
+    );
+    assert_eq!(context("local s = [==[ ]] |]==]"), CursorContext::String);
+}
+
+#[test]
+fn interpolation() {
+    assert_eq!(context("print(`a {|} b`)"), CursorContext::Interpolation);
+    assert_eq!(
+        context("print(`a {foo.b|} b`)"),
+        CursorContext::Interpolation
+    );
+    assert_eq!(context("print(`a {foo} |b`)"), CursorContext::String);
+    assert_eq!(context(r"print(`a \{|} b`)"), CursorContext::String);
+    assert_eq!(context("print(`a {fo|"), CursorContext::Interpolation);
+}
+
+#[test]
+fn numbers() {
+    assert_eq!(context("local x = 12|"), CursorContext::Number);
+    assert_eq!(context("local x = 0x|F"), CursorContext::Number);
+    assert!(!context("local x = 1|").allows_completion());
+}
+
+#[test]
+fn member_access() {
+    assert_eq!(context("foo.|"), CursorContext::MemberAccess);
+    assert_eq!(context("foo.ba|"), CursorContext::MemberAccess);
+    assert_eq!(context("foo:|"), CursorContext::MethodAccess);
+    assert_eq!(context("foo():ba|"), CursorContext::MethodAccess);
+    assert_eq!(context("function Class:|"), CursorContext::MethodAccess);
+    assert!(context("foo.|").allows_completion());
+}
+
+#[test]
+fn types() {
+    assert_eq!(context("local x: |"), CursorContext::TypeAnnotation);
+    assert_eq!(context("local x, y: num|"), CursorContext::TypeAnnotation);
+    assert_eq!(
+        context("function foo(a, b: |)"),
+        CursorContext::TypeAnnotation
+    );
+    assert_eq!(
+        context("local function foo(): |"),
+        CursorContext::TypeAnnotation
+    );
+    assert_eq!(
+        context("function a.b:c(d: |)"),
+        CursorContext::TypeAnnotation
+    );
+    assert_eq!(context("local x = y :: |"), CursorContext::TypeAnnotation);
+    assert_eq!(context("type F = () -> |"), CursorContext::TypeAnnotation);
+    assert_eq!(context("foo(a, b:|)"), CursorContext::MethodAccess);
+}
+
+#[test]
+fn declarations() {
+    assert_eq!(context("local |"), CursorContext::LocalName);
+    assert_eq!(context("local fo|"), CursorContext::LocalName);
+    assert_eq!(context("for i|"), CursorContext::LocalName);
+    assert_eq!(context("local function fo|"), CursorContext::FunctionName);
+    assert!(!context("local |").allows_completion());
+}
+
+#[test]
+fn code() {
+    assert_eq!(context("|"), CursorContext::Code);
+    assert_eq!(context("local x = |"), CursorContext::Code);
+    assert_eq!(context("local x = fo|"), CursorContext::Code);
+    assert_eq!(context("if x then\n    pr|\nend"), CursorContext::Code);
+}
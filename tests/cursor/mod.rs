@@ -0,0 +1,89 @@
+use luau_lexer::prelude::{
+    Keyword, Lexer, Symbol, TokenCursor, TokenKind, TokenPattern, TokenType,
+};
+
+#[test]
+fn peek_and_advance() {
+    let mut cursor = TokenCursor::new(Lexer::new("local x = 1"));
+
+    assert_eq!(cursor.peek(2).token_type, TokenType::Symbol(Symbol::Equal));
+    assert_eq!(
+        cursor.current().token_type,
+        TokenType::Keyword(Keyword::Local)
+    );
+    assert_eq!(
+        cursor.advance().token_type,
+        TokenType::Keyword(Keyword::Local)
+    );
+    assert_eq!(
+        cursor.advance().token_type,
+        TokenType::Identifier("x".into())
+    );
+    assert!(cursor.is_at(Symbol::Equal));
+
+    cursor.advance();
+    cursor.advance();
+    assert!(cursor.is_at_end());
+
+    // The end of the file is returned forever.
+    assert_eq!(cursor.peek(5).token_type, TokenType::EndOfFile);
+    assert_eq!(cursor.advance().token_type, TokenType::EndOfFile);
+}
+
+#[test]
+fn eat() {
+    let mut cursor = TokenCursor::new(Lexer::new("f(a, b)"));
+
+    assert!(cursor.eat(TokenKind::Identifier).is_some());
+    assert!(cursor.eat(Symbol::ClosingParenthesis).is_none());
+    assert!(cursor.eat(Symbol::OpeningParenthesis).is_some());
+    assert!(cursor.at_any(&[TokenKind::Number, TokenKind::Identifier]));
+    assert!(cursor.eat(TokenType::Identifier("a".into())).is_some());
+    assert!(cursor.at_any(&[Symbol::Comma, Symbol::ClosingParenthesis]));
+}
+
+#[test]
+fn expect() {
+    let mut cursor = TokenCursor::new(Lexer::new("if x end"));
+
+    assert!(cursor.expect(Keyword::If).is_ok());
+    assert!(cursor.expect(TokenKind::Identifier).is_ok());
+
+    let error = cursor.expect(Keyword::Then).unwrap_err();
+    assert_eq!(error.message(), "Expected `then`, found `end`.");
+    assert_eq!(error.start().character, 5);
+    assert_eq!(error.end().map(|end| end.character), Some(8));
+
+    // The token isn't consumed on failure.
+    assert!(cursor.expect(Keyword::End).is_ok());
+
+    let error = cursor.expect(Symbol::ClosingParenthesis).unwrap_err();
+    assert_eq!(error.message(), "Expected `)`, found the end of the file.");
+}
+
+#[test]
+fn skip_errors() {
+    let code = "local x = 'a\nlocal y";
+
+    let mut cursor = TokenCursor::new(Lexer::new(code));
+    let kinds = (0..6)
+        .map(|_| cursor.advance().token_type.kind())
+        .collect::<Vec<_>>();
+    assert!(kinds.contains(&TokenKind::Error));
+
+    let mut cursor = TokenCursor::new(Lexer::new(code)).with_skip_errors(true);
+    for _ in 0..4 {
+        cursor.advance();
+    }
+    assert!(cursor.expect(Keyword::Local).is_ok());
+    assert_eq!(cursor.errors().len(), 2);
+    assert_eq!(cursor.take_errors().len(), 2);
+    assert!(cursor.errors().is_empty());
+}
+
+#[test]
+fn describe() {
+    assert_eq!(TokenKind::Identifier.describe(), "an identifier");
+    assert_eq!(Symbol::Arrow.describe(), "`->`");
+    assert_eq!(TokenType::Identifier("foo".into()).describe(), "`foo`");
+}
@@ -0,0 +1,133 @@
+use luau_lexer::prelude::{
+    CompoundOperator, Dialect, Keyword, LexerConfig, Operator, PartialKeyword, Symbol, TokenType,
+};
+
+fn token_types(code: &str, dialect: Dialect) -> Vec<TokenType> {
+    LexerConfig::new()
+        .with_dialect(dialect)
+        .build(code)
+        .collect_tokens()
+        .into_iter()
+        .map(|token| token.token_type)
+        .filter(|token_type| *token_type != TokenType::EndOfFile)
+        .collect()
+}
+
+fn is_error(token_type: &TokenType) -> bool {
+    matches!(token_type, TokenType::Error(_))
+}
+
+#[test]
+fn luau_only_tokens() {
+    let code = "a += 1 :: number -> ? & | @";
+
+    assert!(!token_types(code, Dialect::Luau).iter().any(is_error));
+
+    assert_eq!(
+        token_types("a += 1", Dialect::Lua51)[1..3],
+        [
+            TokenType::Operator(Operator::Plus),
+            TokenType::Symbol(Symbol::Equal),
+        ]
+    );
+    assert_eq!(
+        token_types("a::b", Dialect::Lua51)[1..3],
+        [
+            TokenType::Symbol(Symbol::Colon),
+            TokenType::Symbol(Symbol::Colon),
+        ]
+    );
+    assert_eq!(
+        token_types("-> ==", Dialect::Lua54),
+        [
+            TokenType::Operator(Operator::Minus),
+            TokenType::Symbol(Symbol::ClosingAngleBrackets),
+            TokenType::CompoundOperator(CompoundOperator::EqualEqual),
+        ]
+    );
+
+    for code in ["`string`", "?", "@native", "&", "|"] {
+        assert!(is_error(&token_types(code, Dialect::Lua52)[0]), "{code}");
+    }
+}
+
+#[test]
+fn partial_keywords() {
+    let code = "type continue export typeof";
+
+    assert!(
+        token_types(code, Dialect::Luau)
+            .iter()
+            .all(|token_type| matches!(token_type, TokenType::PartialKeyword(_)))
+    );
+    assert!(
+        token_types(code, Dialect::Lua54)
+            .iter()
+            .all(|token_type| matches!(token_type, TokenType::Identifier(_)))
+    );
+}
+
+#[test]
+fn labels() {
+    assert_eq!(
+        token_types("goto ::", Dialect::Luau)[0],
+        TokenType::Identifier("goto".into())
+    );
+    assert_eq!(
+        token_types("goto continue ::continue::", Dialect::Lua52),
+        [
+            TokenType::Keyword(Keyword::Goto),
+            TokenType::Identifier("continue".into()),
+            TokenType::Symbol(Symbol::DoubleColon),
+            TokenType::Identifier("continue".into()),
+            TokenType::Symbol(Symbol::DoubleColon),
+        ]
+    );
+}
+
+#[test]
+fn bitwise_operators() {
+    assert_eq!(
+        token_types("& | ~ << >> // ~=", Dialect::Lua53),
+        [
+            TokenType::Operator(Operator::BitwiseAnd),
+            TokenType::Operator(Operator::BitwiseOr),
+            TokenType::Operator(Operator::BitwiseXor),
+            TokenType::Operator(Operator::LeftShift),
+            TokenType::Operator(Operator::RightShift),
+            TokenType::Operator(Operator::FloorDivision),
+            TokenType::Operator(Operator::NotEqual),
+        ]
+    );
+    assert_eq!(
+        token_types("//", Dialect::Lua52),
+        [
+            TokenType::Operator(Operator::Division),
+            TokenType::Operator(Operator::Division),
+        ]
+    );
+    assert_eq!(
+        token_types(">>", Dialect::Luau),
+        [
+            TokenType::Symbol(Symbol::ClosingAngleBrackets),
+            TokenType::Symbol(Symbol::ClosingAngleBrackets),
+        ]
+    );
+}
+
+#[test]
+fn attributes() {
+    assert_eq!(
+        token_types("local x <const> = 1", Dialect::Lua54)[1..5],
+        [
+            TokenType::Identifier("x".into()),
+            TokenType::Symbol(Symbol::OpeningAngleBrackets),
+            TokenType::PartialKeyword(PartialKeyword::Const),
+            TokenType::Symbol(Symbol::ClosingAngleBrackets),
+        ]
+    );
+    assert_eq!(
+        token_types("close", Dialect::Lua53),
+        [TokenType::Identifier("close".into())]
+    );
+}
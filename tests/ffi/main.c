@@ -0,0 +1,78 @@
+/* Lexes some code through the C interface, compiled and run by `mod.rs`. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "luau_lexer.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                       \
+    do {                                                                       \
+        if (!(condition)) {                                                    \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,   \
+                    #condition);                                               \
+            failures++;                                                        \
+        }                                                                      \
+    } while (0)
+
+/* Whether or not the token's text is the passed string. */
+static bool text_is(const LuauToken *token, const char *text) {
+    return token->text_length == strlen(text) &&
+           memcmp(token->text, text, token->text_length) == 0;
+}
+
+int main(void) {
+    const char *code = "local s = '\xC3\xA9' -- note\nx = 'oops\n";
+    LuauLexer *lexer = luau_lexer_new(code, strlen(code));
+    LuauToken tokens[16];
+    size_t count = 0;
+
+    CHECK(lexer != NULL);
+    while (count < 16 && luau_lexer_next_token(lexer, &tokens[count])) {
+        count++;
+    }
+
+    CHECK(count == 10);
+    CHECK(tokens[0].kind == LUAU_TOKEN_KEYWORD);
+    CHECK(text_is(&tokens[0], "local"));
+    CHECK(tokens[0].error_index == LUAU_NO_ERROR);
+
+    CHECK(tokens[1].kind == LUAU_TOKEN_IDENTIFIER);
+    CHECK(tokens[2].kind == LUAU_TOKEN_SYMBOL);
+
+    CHECK(tokens[3].kind == LUAU_TOKEN_STRING);
+    CHECK(text_is(&tokens[3], "'\xC3\xA9'"));
+    CHECK(tokens[3].start_offset == 10 && tokens[3].end_offset == 14);
+    CHECK(tokens[3].start_column == 10 && tokens[3].end_column == 13);
+
+    CHECK(tokens[4].kind == LUAU_TOKEN_IDENTIFIER);
+    CHECK(tokens[4].start_line == 1 && tokens[4].start_column == 0);
+    CHECK(text_is(&tokens[4], "x"));
+
+    /* The unterminated string is followed by its errors, which own no text. */
+    CHECK(tokens[6].kind == LUAU_TOKEN_STRING);
+    CHECK(tokens[7].kind == LUAU_TOKEN_ERROR);
+    CHECK(tokens[7].error_index == 0 && tokens[7].text_length == 0);
+    CHECK(tokens[8].error_index == 1);
+    CHECK(tokens[9].kind == LUAU_TOKEN_END_OF_FILE);
+    CHECK(!luau_lexer_next_token(lexer, &tokens[0]));
+
+    LuauError error;
+    CHECK(luau_lexer_error_count(lexer) == 2);
+    CHECK(luau_lexer_error(lexer, 1, &error));
+    CHECK(error.message_length == strlen("Missing ' to close string."));
+    CHECK(memcmp(error.message, "Missing ' to close string.", error.message_length) == 0);
+    CHECK(error.start_line == 1 && error.start_column == 4 && error.end_column == 9);
+    CHECK(!error.is_warning);
+    CHECK(!luau_lexer_error(lexer, 2, &error));
+
+    luau_lexer_free(lexer);
+
+    CHECK(luau_lexer_new("\xFF", 1) == NULL);
+    CHECK(!luau_lexer_next_token(NULL, &tokens[0]));
+    CHECK(luau_lexer_error_count(NULL) == 0);
+    luau_lexer_free(NULL);
+
+    return failures == 0 ? 0 : 1;
+}
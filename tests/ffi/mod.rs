@@ -0,0 +1,92 @@
+use luau_lexer::{ffi, prelude::TokenKind};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+/// Build the crate as a static library with the `ffi` feature and get its path.
+fn build_library() -> PathBuf {
+    let output = Command::new(env!("CARGO"))
+        .args([
+            "rustc",
+            "--lib",
+            "--features",
+            "ffi",
+            "--crate-type",
+            "staticlib",
+        ])
+        .args(["--message-format", "json"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    // The JSON is searched directly to not depend on `serde_json`.
+    let library = stdout
+        .split('"')
+        .find(|part| part.ends_with(".a") || part.ends_with(".lib"))
+        .unwrap_or_else(|| panic!("{}", String::from_utf8_lossy(&output.stderr)));
+
+    PathBuf::from(library)
+}
+
+/// Compile `main.c` against the static library and the header, then run it.
+#[test]
+fn c_interface() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let library = build_library();
+    let executable = env::temp_dir().join(format!("luau-lexer-ffi-{}", process::id()));
+
+    let mut compile = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()));
+    compile
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror"])
+        .arg("-I")
+        .arg(manifest.join("include"))
+        .arg(manifest.join("tests/ffi/main.c"))
+        .arg(&library)
+        .arg("-o")
+        .arg(&executable);
+    if cfg!(target_os = "linux") {
+        compile.args(["-lpthread", "-ldl", "-lm"]);
+    }
+
+    let compiled = compile.output().unwrap();
+    assert!(
+        compiled.status.success(),
+        "{}",
+        String::from_utf8_lossy(&compiled.stderr)
+    );
+
+    let ran = Command::new(&executable).output().unwrap();
+    let _ = fs::remove_file(&executable);
+
+    assert!(
+        ran.status.success(),
+        "{}",
+        String::from_utf8_lossy(&ran.stderr)
+    );
+}
+
+#[test]
+fn kinds() {
+    let kinds = [
+        ffi::LUAU_TOKEN_ERROR,
+        ffi::LUAU_TOKEN_NUMBER,
+        ffi::LUAU_TOKEN_STRING,
+        ffi::LUAU_TOKEN_BOOLEAN,
+        ffi::LUAU_TOKEN_IDENTIFIER,
+        ffi::LUAU_TOKEN_COMMENT,
+        ffi::LUAU_TOKEN_KEYWORD,
+        ffi::LUAU_TOKEN_PARTIAL_KEYWORD,
+        ffi::LUAU_TOKEN_SYMBOL,
+        ffi::LUAU_TOKEN_OPERATOR,
+        ffi::LUAU_TOKEN_COMPOUND_OPERATOR,
+        ffi::LUAU_TOKEN_END_OF_FILE,
+        ffi::LUAU_TOKEN_SPACES,
+    ];
+
+    for (kind, constant) in TokenKind::ALL.into_iter().zip(kinds) {
+        assert_eq!(kind as u32, constant, "{kind:?}");
+    }
+}
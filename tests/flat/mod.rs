@@ -0,0 +1,159 @@
+use std::mem;
+
+use lsp_types::Position;
+use luau_lexer::prelude::{Lexer, LexerConfig, Token, TokenType, Trivia, TriviaType};
+
+#[test]
+fn spaces_as_tokens() {
+    let code = "local  x\n";
+    let tokens = LexerConfig::new()
+        .with_spaces_as_tokens(true)
+        .build(code)
+        .collect_tokens();
+
+    assert_eq!(tokens.len(), 5);
+    assert_eq!(tokens[1], TokenType::Spaces("  ".into()));
+    assert_eq!(tokens[1].start, Position::new(0, 5));
+    assert_eq!(tokens[1].end, Position::new(0, 7));
+    assert_eq!(tokens[3], TokenType::Spaces("\n".into()));
+    assert!(
+        tokens
+            .iter()
+            .all(|token| token.leading_trivia.is_empty() && token.trailing_trivia.is_empty())
+    );
+}
+
+/// Code covering every kind of trivia, for the tests comparing lexing modes.
+const SAMPLES: [&str; 7] = [
+    "",
+    "  \n",
+    "--!strict\nlocal x = 1 -- one\n",
+    "\t--[[ a ]]  --[==[\nb\n]==]\nreturn x--c",
+    "export type function f() end",
+    "local s = 'oops\nlocal t = `a{b}c` $ --[[ unterminated",
+    "x\u{a0}=\u{3000}1\r\n",
+];
+
+/// Get the byte offset of the passed position, with the default tab width.
+fn offset(code: &str, position: Position) -> usize {
+    let line = code
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(str::len)
+        .sum::<usize>();
+    let character = code[line..]
+        .chars()
+        .take(position.character as usize)
+        .map(char::len_utf8)
+        .sum::<usize>();
+
+    line + character
+}
+
+/// Fold [`TokenType::Spaces`] back into the trivia of the tokens around them.
+fn fold_spaces(code: &str, tokens: Vec<Token>) -> Vec<Token> {
+    let mut folded: Vec<Token> = Vec::new();
+    let mut previous = None;
+    let mut trivia = tokens
+        .first()
+        .map(|token| token.leading_trivia.clone())
+        .unwrap_or_default();
+
+    for mut token in tokens {
+        match token.token_type {
+            TokenType::Spaces(spaces) => {
+                trivia.push(TriviaType::Spaces(spaces).into_trivia(
+                    token.start,
+                    token.end,
+                    offset(code, token.start),
+                    offset(code, token.end),
+                ));
+                trivia.append(&mut token.trailing_trivia);
+            }
+            // Queued errors don't own any text, so they never have trivia.
+            TokenType::Error(_) if token.start == token.end => folded.push(token),
+            _ => {
+                if let Some(previous) = previous {
+                    let previous: &mut Token = &mut folded[previous];
+                    previous.trailing_trivia.clone_from(&trivia);
+                }
+
+                token.leading_trivia = mem::replace(&mut trivia, token.trailing_trivia.clone());
+                previous = Some(folded.len());
+                folded.push(token);
+            }
+        }
+    }
+
+    folded
+}
+
+#[test]
+fn folded_spaces() {
+    for code in SAMPLES {
+        let tokens = LexerConfig::new()
+            .with_spaces_as_tokens(true)
+            .build(code)
+            .collect_tokens();
+
+        assert_eq!(
+            fold_spaces(code, tokens),
+            Lexer::new(code).collect_tokens(),
+            "{code:?}"
+        );
+    }
+}
+
+/// Flatten tokens into a single stream, with all trivia as tokens.
+fn flatten(tokens: &[Token]) -> Vec<(TokenType, Position, Position)> {
+    let trivia = |trivia: &[Trivia]| {
+        trivia
+            .iter()
+            .map(|trivia| {
+                let token_type = match &trivia.trivia_type {
+                    TriviaType::Spaces(spaces) => TokenType::Spaces(spaces.clone()),
+                    TriviaType::Comment(comment) => TokenType::Comment(comment.clone()),
+                };
+
+                (token_type, trivia.start, trivia.end)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut flat = tokens
+        .first()
+        .map(|token| trivia(&token.leading_trivia))
+        .unwrap_or_default();
+    for token in tokens {
+        flat.push((token.token_type.clone(), token.start, token.end));
+        flat.extend(trivia(&token.trailing_trivia));
+    }
+
+    flat
+}
+
+#[test]
+fn flat_stream() {
+    for code in SAMPLES {
+        let expected = flatten(&Lexer::new(code).collect_tokens());
+
+        for (comments, spaces) in [(true, false), (false, true), (true, true)] {
+            let tokens = LexerConfig::new()
+                .with_comments_as_tokens(comments)
+                .with_spaces_as_tokens(spaces)
+                .build(code)
+                .collect_tokens();
+
+            assert_eq!(flatten(&tokens), expected, "{code:?} {comments} {spaces}");
+            if comments && spaces {
+                assert!(
+                    tokens
+                        .iter()
+                        .all(|token| token.leading_trivia.is_empty()
+                            && token.trailing_trivia.is_empty()),
+                    "{code:?}"
+                );
+            }
+        }
+    }
+}
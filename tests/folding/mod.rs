@@ -0,0 +1,120 @@
+use lsp_types::FoldingRangeKind;
+use luau_lexer::prelude::{Lexer, LexerConfig, folding_ranges};
+
+fn ranges(code: &str) -> Vec<(u32, u32, Option<FoldingRangeKind>)> {
+    folding_ranges(&Lexer::new(code).collect_tokens())
+        .into_iter()
+        .map(|range| (range.start_line, range.end_line, range.kind))
+        .collect()
+}
+
+#[test]
+fn blocks() {
+    let code = r#"local function foo()
+    if a then
+        print(1)
+    else
+        print(2)
+    end
+end
+
+local t = {
+    a = 1,
+    b = 2,
+}
+print(
+    1,
+    2
+)"#;
+
+    assert_eq!(
+        ranges(code),
+        [
+            (0, 5, None),
+            (1, 2, None),
+            (3, 4, None),
+            (8, 10, None),
+            (12, 14, None)
+        ]
+    );
+}
+
+#[test]
+fn unclosed_blocks() {
+    let code = "local function foo()\n    print(1)\n    print(2)\n";
+
+    assert_eq!(ranges(code), [(0, 2, None)]);
+}
+
+#[test]
+fn strings_and_comments() {
+    let code = r#"-- A header
+-- spanning
+-- three lines
+
+--[[
+    A block comment.
+]]
+local x = [[
+multi-line
+]] -- Not part of a run.
+-- Only one line.
+"#;
+
+    assert_eq!(
+        ranges(code),
+        [
+            (0, 2, Some(FoldingRangeKind::Comment)),
+            (4, 6, Some(FoldingRangeKind::Comment)),
+            (7, 9, None),
+        ]
+    );
+}
+
+#[test]
+fn comments_as_tokens() {
+    let code = "-- a\n-- b\nlocal x = 1\n--[[\n]]";
+    let tokens = LexerConfig::new()
+        .with_comments_as_tokens(true)
+        .build(code)
+        .collect_tokens();
+    let ranges = folding_ranges(&tokens);
+
+    assert_eq!(ranges.len(), 2);
+    assert_eq!((ranges[0].start_line, ranges[0].end_line), (0, 1));
+    assert_eq!((ranges[1].start_line, ranges[1].end_line), (3, 4));
+}
+
+#[test]
+fn imports_and_regions() {
+    let code = r#"--!strict
+local A = require(script.A)
+local B = require(script.B).B;
+local C = require("./C")
+
+--#region Helpers
+local function helper() end
+--#endregion
+"#;
+
+    assert_eq!(
+        ranges(code),
+        [
+            (1, 3, Some(FoldingRangeKind::Imports)),
+            (5, 7, Some(FoldingRangeKind::Region)),
+        ]
+    );
+}
+
+#[test]
+fn only_leading_requires() {
+    let code = r#"local A = require(script.A)
+-- Shared.
+local B = require(script.B)
+print(A, B)
+local C = require(script.C)
+local D = require(script.D)
+"#;
+
+    assert_eq!(ranges(code), [(0, 2, Some(FoldingRangeKind::Imports))]);
+}
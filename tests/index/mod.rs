@@ -0,0 +1,83 @@
+use lsp_types::{DocumentHighlightKind, Position, Uri};
+use luau_lexer::prelude::{IdentifierIndex, Lexer};
+use std::str::FromStr;
+
+const CODE: &str = "local foo = 1
+foo += bar(foo)
+local type = typeof(foo)
+type Foo = number
+print(type, t.type)";
+
+fn index() -> IdentifierIndex {
+    IdentifierIndex::new(&Lexer::new(CODE).collect_tokens())
+}
+
+#[test]
+fn occurrences() {
+    let index = index();
+
+    let foo = index.occurrences("foo");
+    assert_eq!(foo.len(), 4);
+    assert_eq!(foo[0].range.start, Position::new(0, 6));
+    assert_eq!(foo[1].range.start, Position::new(1, 0));
+    assert!(foo[0].is_write && foo[1].is_write && !foo[2].is_write);
+
+    // `type` as a keyword isn't indexed, but as an identifier it is.
+    assert_eq!(index.occurrences("type").len(), 3);
+    assert_eq!(index.occurrences("typeof").len(), 1);
+    assert!(index.occurrences("local").is_empty());
+    assert!(index.names().any(|name| name == "Foo"));
+}
+
+#[test]
+fn highlights() {
+    let index = index();
+
+    assert_eq!(index.name_at(Position::new(1, 12)), Some("foo"));
+    assert_eq!(index.name_at(Position::new(1, 3)), Some("foo"));
+    assert_eq!(index.name_at(Position::new(1, 10)), Some("bar"));
+    assert_eq!(index.name_at(Position::new(1, 5)), None);
+
+    let highlights = index.highlights(Position::new(1, 11));
+    assert_eq!(highlights.len(), 4);
+    assert_eq!(highlights[0].kind, Some(DocumentHighlightKind::WRITE));
+    assert_eq!(highlights[2].kind, Some(DocumentHighlightKind::READ));
+    assert!(index.highlights(Position::new(3, 10)).is_empty());
+}
+
+#[test]
+fn rename() {
+    let index = index();
+
+    let edits = index.rename("foo", "renamed").unwrap();
+    assert_eq!(edits.len(), 4);
+    assert!(edits.iter().all(|edit| edit.new_text == "renamed"));
+
+    let ranges = index.occurrences("foo")[1..]
+        .iter()
+        .map(|occurrence| occurrence.range);
+    assert_eq!(
+        IdentifierIndex::rename_ranges(ranges, "bar").unwrap().len(),
+        3
+    );
+
+    let uri = Uri::from_str("file:///foo.luau").unwrap();
+    let edit = index
+        .rename_workspace_edit(uri.clone(), "foo", "baz")
+        .unwrap();
+    assert_eq!(edit.changes.unwrap()[&uri].len(), 4);
+}
+
+#[test]
+fn invalid_names() {
+    let index = index();
+
+    for name in ["end", "function", "true", "and", "not", "1st", "a-b", ""] {
+        let error = index.rename("foo", name).unwrap_err();
+        assert_eq!(error.start(), Position::new(0, 6));
+    }
+
+    // Contextual keywords are valid identifiers.
+    assert!(index.rename("foo", "type").is_ok());
+    assert!(index.rename("foo", "continue").is_ok());
+}
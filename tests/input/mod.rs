@@ -0,0 +1,66 @@
+use luau_lexer::prelude::{ChunkedInput, Lexer, LexerConfig, LexerInput};
+
+const CODE: &str = r#"--!strict
+-- A comment with ünïcödé.
+local message: string = `hello {name}, you owe {0x_FF + 1.5e3}`
+local long = [==[
+multi ✓ line
+]==] --[[ block
+comment ]]
+type function foo(a, ...) return a // 2 end
+x += 0b1010 $ 'tail\'s' "end"
+"#;
+
+/// Split the passed code into chunks of at most `size` bytes, never splitting
+/// a character.
+fn chunked(code: &str, size: usize) -> ChunkedInput {
+    let mut input = ChunkedInput::new();
+    let mut chunk = String::new();
+
+    for character in code.chars() {
+        if chunk.len() + character.len_utf8() > size && !chunk.is_empty() {
+            input.push(std::mem::take(&mut chunk));
+        }
+        chunk.push(character);
+    }
+    input.push(chunk);
+
+    input
+}
+
+#[test]
+fn tiny_chunks() {
+    let expected = Lexer::new(CODE).collect_tokens();
+
+    for size in 1..=4 {
+        let input = chunked(CODE, size);
+        assert!(input.chunks().len() > CODE.len() / 4);
+
+        let tokens = Lexer::from_input(input).collect_tokens();
+        assert_eq!(tokens, expected, "chunks of {size} bytes");
+    }
+}
+
+#[test]
+fn borrowed_input() {
+    let expected = Lexer::new(CODE).collect_tokens();
+
+    assert_eq!(Lexer::from_input(CODE).collect_tokens(), expected);
+    assert_eq!(
+        LexerConfig::new().build_from(CODE).collect_tokens(),
+        expected
+    );
+}
+
+#[test]
+fn chunked_slices() {
+    let input = ChunkedInput::from_iter(["ab", "ç", "de"]);
+
+    assert_eq!(input.len(), 6);
+    assert_eq!(input.char_at(2), Some('ç'));
+    assert_eq!(input.byte_at(4), Some(b'd'));
+    assert_eq!(input.char_at(6), None);
+    assert_eq!(input.slice(0..2), "ab");
+    assert_eq!(input.slice(1..5), "bçd");
+    assert_eq!(input.slice(0..6), "abçde");
+}
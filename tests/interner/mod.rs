@@ -0,0 +1,86 @@
+use luau_lexer::prelude::{Interner, Lexer, Literal, LuauString, TokenType};
+use smol_str::SmolStr;
+
+const LONG_NAME: &str = "a_very_long_identifier_name_that_allocates";
+
+/// Get the text of all identifiers and strings in the passed tokens.
+fn texts(lexer: &mut Lexer) -> Vec<SmolStr> {
+    lexer
+        .collect_tokens()
+        .into_iter()
+        .filter_map(|token| match token.token_type {
+            TokenType::Identifier(text)
+            | TokenType::Literal(Literal::String(LuauString::DoubleQuotes(text))) => Some(text),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn shared_across_files() {
+    let first = format!("local {LONG_NAME} = \"a string which is long enough to allocate\"");
+    let second = format!("print({LONG_NAME}, \"a string which is long enough to allocate\")");
+
+    let mut lexer = Lexer::new(&first).with_interner(Interner::new());
+    let first_texts = texts(&mut lexer);
+
+    let mut lexer = Lexer::new(&second).with_interner(lexer.take_interner().unwrap());
+    let second_texts = texts(&mut lexer);
+    let interner = lexer.interner().unwrap();
+
+    assert_eq!(interner.len(), 3);
+    assert_eq!(first_texts[0], second_texts[1]);
+    assert_eq!(first_texts[0].as_ptr(), second_texts[1].as_ptr());
+    assert_eq!(first_texts[1].as_ptr(), second_texts[2].as_ptr());
+
+    let id = interner.get(LONG_NAME).unwrap();
+    assert_eq!(interner.resolve(id), Some(LONG_NAME));
+    assert_eq!(interner.get("missing"), None);
+}
+
+#[test]
+fn intern() {
+    let mut interner = Interner::new();
+
+    let a = interner.intern("a");
+    let b = interner.intern("b");
+
+    assert_ne!(a, b);
+    assert_eq!(interner.intern("a"), a);
+    assert_eq!(interner.resolve(b), Some("b"));
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn without_interner() {
+    let code = format!("{LONG_NAME} {LONG_NAME}");
+    let texts = texts(&mut Lexer::new(&code));
+
+    assert_ne!(texts[0].as_ptr(), texts[1].as_ptr());
+    assert_eq!(
+        texts,
+        self::texts(&mut Lexer::new(&code).with_interner(Interner::new()))
+    );
+}
+
+#[test]
+fn ids() {
+    let code = format!("local {LONG_NAME} = {LONG_NAME} .. '{LONG_NAME}'");
+    let mut lexer = Lexer::new(&code).with_interner(Interner::new());
+    let (tokens, ids) = lexer.collect_tokens_with_ids();
+
+    assert_eq!(tokens.len(), ids.len());
+    assert_eq!(ids[0], None);
+    assert_eq!(ids[1], ids[3]);
+    assert_ne!(ids[3], ids[5]);
+
+    let interner = lexer.interner().unwrap();
+    assert_eq!(interner.resolve(ids[1].unwrap()), Some(LONG_NAME));
+    assert_eq!(
+        interner.resolve(ids[5].unwrap()),
+        Some(format!("'{LONG_NAME}'").as_str())
+    );
+
+    let (_, ids) = Lexer::new(&code).collect_tokens_with_ids();
+    assert!(ids.iter().all(Option::is_none));
+}
@@ -1,2 +1,28 @@
 #![cfg(test)]
+mod cache;
+mod checkpoint;
+#[cfg(feature = "cli")]
+mod cli;
+mod config;
+mod context;
+mod cursor;
+mod dialect;
+#[cfg(all(feature = "ffi", unix))]
+mod ffi;
+mod flat;
+mod folding;
+mod index;
+mod input;
+mod interner;
+mod markdown;
+mod minifier;
+mod render;
+mod reuse;
+#[cfg(feature = "schema")]
+mod schema;
+mod store;
+mod strict;
 mod token;
+mod tree;
+mod version;
+mod workspace;
@@ -0,0 +1,79 @@
+use lsp_types::Position;
+use luau_lexer::prelude::{Keyword, LexerConfig, TokenType, code_fences, lex_code_fences};
+
+const MARKDOWN: &str = "# Example
+
+```lua
+local x = 1
+```
+
+```js
+const y = 2;
+```
+
+  ~~~~Luau title
+  print('oops)
+  ~~~~
+
+```luau
+return x
+";
+
+#[test]
+fn start() {
+    let document = "ignored\nlocal x = 'oops\n";
+    let mut lexer = LexerConfig::new()
+        .build_from(document)
+        .with_start(8, Position::new(1, 0));
+
+    let token = lexer.next_token();
+    assert_eq!(token, TokenType::Keyword(Keyword::Local));
+    assert_eq!(token.start, Position::new(1, 0));
+    assert_eq!(lexer.offset(), 14);
+
+    let tokens = lexer.collect_tokens();
+    let TokenType::Error(error) = &tokens[3].token_type else {
+        panic!("expected an error, got {:?}", tokens[3]);
+    };
+    assert_eq!(error.start(), Position::new(1, 10));
+}
+
+#[test]
+fn fences() {
+    let fences = code_fences(MARKDOWN);
+    let languages = fences
+        .iter()
+        .map(|fence| fence.language)
+        .collect::<Vec<_>>();
+
+    assert_eq!(languages, ["lua", "Luau", "luau"]);
+    assert_eq!(fences[0].code, "local x = 1\n");
+    assert_eq!(fences[0].position, Position::new(3, 0));
+    assert_eq!(
+        &MARKDOWN[fences[1].offset..],
+        "  print('oops)\n  ~~~~\n\n```luau\nreturn x\n"
+    );
+    assert_eq!(fences[2].code, "return x\n");
+}
+
+#[test]
+fn lexed() {
+    let lexed = lex_code_fences(MARKDOWN, LexerConfig::new());
+    assert_eq!(lexed.len(), 3);
+
+    let (_, tokens) = &lexed[0];
+    assert_eq!(tokens[1].start, Position::new(3, 6));
+    assert_eq!(tokens.last().unwrap().end, Position::new(4, 0));
+
+    let (_, tokens) = &lexed[1];
+    assert_eq!(tokens[0].start, Position::new(11, 2));
+    let errors = tokens
+        .iter()
+        .filter_map(|token| match &token.token_type {
+            TokenType::Error(error) => Some(error.start()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(!errors.is_empty());
+    assert!(errors.iter().all(|start| *start == Position::new(11, 8)));
+}
@@ -0,0 +1,67 @@
+use luau_lexer::prelude::{Lexer, Minifier, TokenType};
+
+fn token_types(code: &str) -> Vec<TokenType> {
+    Lexer::new(code)
+        .collect_tokens()
+        .into_iter()
+        .map(|token| token.token_type)
+        .collect()
+}
+
+macro_rules! generate_minify_tests {
+    ($( $fn_name: ident: $code: literal => $expected: literal ),* $(,)?) => {
+        $(
+            #[test]
+            fn $fn_name() {
+                let minified = Minifier::new().minify($code).unwrap();
+
+                assert_eq!(minified, $expected);
+                assert_eq!(token_types(&minified), token_types($code));
+            }
+        )*
+    };
+}
+
+generate_minify_tests!(
+    statements: "local  x = 1\nlocal y = x + 2 -- comment\n" => "local x=1 local y=x+2",
+    identifiers_and_keywords: "if a then return b end" => "if a then return b end",
+    double_minus: "local z = a - -b" => "local z=a- -b",
+    minus_spaces: "x - - y" => "x- -y",
+    concatenation_after_number: "print(1 .. x)" => "print(1 ..x)",
+    concatenation_before_number: "print(x .. 1)" => "print(x..1)",
+    dots: "local a = ... .. b" => "local a=.....b",
+    index_with_string: "t[ [[key]] ] = t[ [=[k]=] ]" => "t[ [[key]]]=t[ [=[k]=]]",
+    index_with_equals: "t[ a ] = b == c" => "t[a]=b==c",
+    number_before_keyword: "local a = 1 local b = 0x1F end" => "local a=1 local b=0x1F end",
+    typecast: "local a = b :: number" => "local a=b::number",
+    comparisons: "a < = b" => "a< =b",
+    compound: "x += 1 y ..= z" => "x+=1 y..=z",
+    floor_division: "a / / b" => "a/ /b",
+    comments: "--[[ block ]] local a --[==[ x ]==] = 1" => "local a=1",
+    decimal_after_concatenation: "a = b .. .5" => "a=b.. .5",
+    negative_number: "a = 1 - -2" => "a=1- -2",
+    strings: "a = 'x' .. \"y\" .. [[z]]" => "a='x'..\"y\"..[[z]]",
+);
+
+#[test]
+fn directives() {
+    let code = "--!strict\n--!native\n-- Copyright\nlocal a = 1";
+
+    assert_eq!(
+        Minifier::new().minify(code).unwrap(),
+        "--!strict\n--!native\nlocal a=1"
+    );
+    assert_eq!(
+        Minifier::new().keep_directives(false).minify(code).unwrap(),
+        "local a=1"
+    );
+    assert_eq!(
+        Minifier::new().keep_header(true).minify(code).unwrap(),
+        "--!strict\n--!native\n-- Copyright\nlocal a=1"
+    );
+}
+
+#[test]
+fn erroneous_code() {
+    assert!(Minifier::new().minify("local a = 'unterminated").is_err());
+}
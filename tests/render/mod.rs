@@ -0,0 +1,134 @@
+use luau_lexer::prelude::{
+    AnsiColor, AnsiRenderer, AnsiStyle, HtmlEscape, HtmlRenderer, Lexer, Theme, Token, TokenKind,
+    TokenType,
+};
+
+const CODE: &str = r#"--!strict
+local foo = "a < b" -- trailing
+--[[ block
+   comment ]]
+if foo ~= nil then $ print(`x`, 0x1F) end
+local broken = "unterminated
+"#;
+
+fn lex(code: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(code);
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = lexer.next_token();
+        let is_eof = token == TokenType::EndOfFile;
+        tokens.push(token);
+
+        if is_eof {
+            break tokens;
+        }
+    }
+}
+
+fn strip_html(html: &str) -> String {
+    let mut output = String::new();
+    let mut in_tag = false;
+
+    for character in html.chars() {
+        match character {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => output.push(character),
+            _ => (),
+        }
+    }
+
+    output
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn strip_ansi(ansi: &str) -> String {
+    let mut output = String::new();
+    let mut characters = ansi.chars();
+
+    while let Some(character) = characters.next() {
+        if character == '\x1b' {
+            characters.by_ref().find(|character| *character == 'm');
+        } else {
+            output.push(character);
+        }
+    }
+
+    output
+}
+
+#[test]
+fn html_preserves_source() {
+    let html = HtmlRenderer::new().render(CODE, &lex(CODE));
+
+    assert_eq!(strip_html(&html), CODE);
+}
+
+#[test]
+fn html_spans() {
+    let code = "local x = 1 -- <hi>";
+    let html = HtmlRenderer::new().render(code, &lex(code));
+
+    assert_eq!(
+        html,
+        "<span class=\"kw\">local</span> x <span class=\"sym\">=</span> \
+         <span class=\"num\">1</span> <span class=\"com\">-- &lt;hi&gt;</span>"
+    );
+}
+
+#[test]
+fn html_errors_own_no_text() {
+    // Both tokens are followed by errors about them, which don't cover the
+    // spaces after them.
+    let code = "local s = 'oops\nreturn 0x end";
+    let html = HtmlRenderer::new().render(code, &lex(code));
+
+    assert_eq!(
+        html,
+        "<span class=\"kw\">local</span> s <span class=\"sym\">=</span> \
+         <span class=\"str\">&#39;oops</span>\n<span class=\"kw\">return</span> \
+         <span class=\"num\">0x</span> <span class=\"kw\">end</span>"
+    );
+}
+
+#[test]
+fn html_custom_theme_and_escape() {
+    let code = "'\"&'";
+    let theme = Theme::new().with(TokenKind::String, "luau-string".into());
+    let html = HtmlRenderer::with_theme(theme)
+        .with_escape(HtmlEscape::Minimal)
+        .render(code, &lex(code));
+
+    assert_eq!(html, "<span class=\"luau-string\">'\"&amp;'</span>");
+}
+
+#[test]
+fn ansi_preserves_source() {
+    let tokens = lex(CODE);
+
+    for renderer in [
+        AnsiRenderer::default(),
+        AnsiRenderer::new(AnsiRenderer::truecolor_theme()),
+    ] {
+        assert_eq!(strip_ansi(&renderer.render(CODE, &tokens)), CODE);
+    }
+}
+
+#[test]
+fn ansi_styles() {
+    let code = "nil";
+    let theme = Theme::new().with(
+        TokenKind::Keyword,
+        AnsiStyle::new(AnsiColor::Rgb(1, 2, 3)).bold(),
+    );
+
+    assert_eq!(
+        AnsiRenderer::new(theme).render(code, &lex(code)),
+        "\x1b[1;38;2;1;2;3mnil\x1b[0m"
+    );
+}
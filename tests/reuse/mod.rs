@@ -0,0 +1,121 @@
+use luau_lexer::prelude::{Interner, Lexer, Token, TokenType};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// The system allocator, counting the allocations of each thread so tests
+/// running in parallel don't affect each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// SAFETY: Everything is forwarded to the system allocator.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        // SAFETY: Same contract as `GlobalAlloc::alloc`.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: Same contract as `GlobalAlloc::dealloc`.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        // SAFETY: Same contract as `GlobalAlloc::realloc`.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Get the number of allocations made by the current thread while running the
+/// passed function.
+fn count_allocations(function: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    function();
+
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+const FILES: [&str; 3] = [
+    "local x = 1\n-- comment\nprint(x, 'text')\n",
+    "--[[ block ]]\nfor i = 1, 10 do\n\tx += i\nend\n",
+    "type Point = { x: number, y: number }\nreturn `{x} and {y}`\n",
+];
+
+#[test]
+fn reset() {
+    let mut lexer = Lexer::new(FILES[0]);
+    lexer.collect_tokens();
+
+    for file in FILES {
+        lexer.reset(file);
+        assert_eq!(lexer.collect_tokens(), Lexer::new(file).collect_tokens());
+    }
+}
+
+#[test]
+fn batch() {
+    let mut tokens = vec![Vec::new(); FILES.len()];
+    Lexer::default().lex_batch(FILES, |index, token| tokens[index].push(token.clone()));
+
+    for (file, tokens) in FILES.into_iter().zip(tokens) {
+        assert_eq!(tokens, Lexer::new(file).collect_tokens());
+    }
+}
+
+#[test]
+fn next_token_into() {
+    let mut lexer = Lexer::new("local x = 'oops\ny = 1");
+    let mut token = Token::empty(TokenType::EndOfFile);
+
+    for expected in Lexer::new("local x = 'oops\ny = 1").collect_tokens() {
+        lexer.next_token_into(&mut token);
+        assert_eq!(token, expected);
+    }
+}
+
+#[test]
+fn steady_state_allocations() {
+    let mut lexer = Lexer::default().with_interner(Interner::new());
+    lexer.lex_batch(FILES, |_, _| ());
+    let mut interner = lexer.take_interner();
+
+    // Lexing the files many times allocates as much as lexing them once, as
+    // everything is reused once the buffers grew.
+    let allocations = [1, 100].map(|repeats| {
+        let mut lexer = Lexer::default();
+        lexer.set_interner(interner.take());
+
+        let inputs = FILES.into_iter().cycle().take(FILES.len() * repeats);
+        let allocations = count_allocations(|| lexer.lex_batch(inputs, |_, _| ()));
+
+        interner = lexer.take_interner();
+        allocations
+    });
+
+    assert_eq!(allocations[0], allocations[1]);
+}
+
+#[test]
+fn interned_texts_allocate_once() {
+    let code = "local a_very_long_identifier_name = 'a string long enough to allocate'\n\
+        print(a_very_long_identifier_name, 'a string long enough to allocate')\n";
+
+    let mut lexer = Lexer::default().with_interner(Interner::new());
+    lexer.lex_batch([code], |_, _| ());
+
+    // Known texts are looked up straight from the input, so only the first
+    // time they're met allocates.
+    let allocations = [1, 10]
+        .map(|repeats| count_allocations(|| lexer.lex_batch(vec![code; repeats], |_, _| ())));
+
+    assert_eq!(allocations[0], allocations[1]);
+}
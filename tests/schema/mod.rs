@@ -0,0 +1,78 @@
+use luau_lexer::prelude::{
+    RecordKind, SCHEMA_VERSION, SourceLocation, TokenDump, TriviaRecordKind, json_schema,
+};
+use std::{env, fs, path::Path};
+
+#[test]
+fn schema_file() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("schema/tokens.schema.json");
+    let schema = serde_json::to_string_pretty(&json_schema()).unwrap() + "\n";
+
+    if env::var_os("UPDATE_SCHEMA").is_some() {
+        fs::write(&path, &schema).unwrap();
+    }
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap_or_default(),
+        schema,
+        "`schema/tokens.schema.json` is outdated, run the tests with `UPDATE_SCHEMA=1`."
+    );
+}
+
+#[test]
+fn records() {
+    let dump = TokenDump::new("local x = 'é'\n\tfoo -- comment");
+    let json = serde_json::to_value(&dump).unwrap();
+
+    assert_eq!(dump.schema_version, SCHEMA_VERSION);
+    assert_eq!(json["schema_version"], SCHEMA_VERSION);
+    assert_eq!(json["tokens"][0]["kind"], "keyword");
+    assert_eq!(json["tokens"][1]["kind"], "identifier");
+    assert_eq!(json["tokens"][2]["kind"], "symbol");
+    assert_eq!(json["tokens"][5]["kind"], "end_of_file");
+    assert!(json["tokens"][0].get("error").is_none());
+
+    let string = &dump.tokens[3];
+    assert_eq!(string.kind, RecordKind::String);
+    assert_eq!(string.text, "'é'");
+    assert_eq!(string.range.start.offset, 10);
+    assert_eq!(string.range.end.offset, 14);
+    assert_eq!(string.range.end.column, 13);
+
+    let identifier = &dump.tokens[4];
+    assert_eq!(identifier.text, "foo");
+    assert_eq!(
+        identifier.range.start,
+        SourceLocation {
+            offset: 16,
+            line: 1,
+            column: 1,
+        }
+    );
+    assert_eq!(identifier.leading_trivia, string.trailing_trivia);
+    assert_eq!(
+        identifier.trailing_trivia[1].kind,
+        TriviaRecordKind::Comment
+    );
+    assert_eq!(identifier.trailing_trivia[1].text, "-- comment");
+    assert_eq!(identifier.trailing_trivia[1].range.start.offset, 20);
+    assert_eq!(identifier.trailing_trivia[1].range.end.offset, 30);
+}
+
+#[test]
+fn errors() {
+    let dump = TokenDump::new("local a = 'oops\nb");
+    let error = dump
+        .tokens
+        .iter()
+        .find(|token| token.kind == RecordKind::Error)
+        .unwrap();
+    let record = error.error.as_ref().unwrap();
+
+    assert!(record.message.starts_with("Strings must be single line"));
+    assert_eq!(record.range.start.offset, 10);
+    assert_eq!(dump.tokens.last().unwrap().kind, RecordKind::EndOfFile);
+
+    let json = serde_json::to_string(&dump).unwrap();
+    assert_eq!(serde_json::from_str::<TokenDump>(&json).unwrap(), dump);
+}
@@ -0,0 +1,108 @@
+use lsp_types::Position;
+use luau_lexer::prelude::{Bias, TokenLocation, TokenStore, TokenType};
+
+const CODE: &str = "-- header\nlocal héllo = a.b -- note\n\tprint(héllo)";
+
+#[test]
+fn offsets() {
+    let store = TokenStore::new(CODE);
+
+    for (index, token) in store.tokens().iter().enumerate() {
+        let range = store.range(index).unwrap();
+        if let Some(text) = token.token_type.try_as_string() {
+            assert_eq!(CODE[range].to_string(), text);
+        }
+    }
+}
+
+#[test]
+fn bias() {
+    let store = TokenStore::new(CODE);
+    let dot = CODE.find('.').unwrap();
+
+    // Between `a` and `.`.
+    let left = store.at_offset(dot, Bias::Left).unwrap();
+    let right = store.at_offset(dot, Bias::Right).unwrap();
+    assert_eq!(left.token.token_type, TokenType::Identifier("a".into()));
+    assert_eq!(right.token.token_type.try_as_string().unwrap(), ".");
+    assert_eq!(left.location, TokenLocation::Token);
+
+    // Between `local` and the space after it.
+    let left = store.at_position(Position::new(1, 5), Bias::Left).unwrap();
+    let right = store.at_position(Position::new(1, 5), Bias::Right).unwrap();
+    assert_eq!(left.location, TokenLocation::Token);
+    assert_eq!(right.location, TokenLocation::TrailingTrivia(0));
+    assert_eq!(left.index, right.index);
+}
+
+#[test]
+fn trivia_and_comments() {
+    let store = TokenStore::new(CODE);
+
+    let header = store.at_position(Position::new(0, 3), Bias::Right).unwrap();
+    assert_eq!(header.location, TokenLocation::LeadingTrivia(0));
+    assert!(header.is_in_comment());
+
+    let note = store
+        .at_offset(CODE.find("note").unwrap(), Bias::Left)
+        .unwrap();
+    assert_eq!(note.token.token_type, TokenType::Identifier("b".into()));
+    assert_eq!(note.location, TokenLocation::TrailingTrivia(1));
+    assert!(note.is_in_comment());
+
+    // Tabs are counted as configured in the lexer.
+    let print = store.at_position(Position::new(2, 1), Bias::Right).unwrap();
+    assert_eq!(
+        print.token.token_type,
+        TokenType::Identifier("print".into())
+    );
+    assert!(!print.is_in_comment());
+
+    let end = store.at_offset(CODE.len() + 10, Bias::Right).unwrap();
+    assert_eq!(end.token.token_type, TokenType::EndOfFile);
+}
+
+#[test]
+fn navigation() {
+    let store = TokenStore::new("a $ b");
+
+    assert_eq!(store.next(0), Some(1));
+    assert!(matches!(
+        store.get(1).unwrap().token_type,
+        TokenType::Error(_)
+    ));
+    assert_eq!(store.next(1), Some(2));
+    assert_eq!(store.previous(2), Some(1));
+    assert_eq!(store.previous(0), None);
+}
+
+#[test]
+fn skips_zero_width_errors() {
+    let store = TokenStore::new("a 'unfinished\nb");
+    let errors = (0..store.len())
+        .filter(|index| matches!(store.get(*index).unwrap().token_type, TokenType::Error(_)))
+        .collect::<Vec<_>>();
+
+    assert!(!errors.is_empty());
+    for error in &errors {
+        assert_eq!(store.range(*error).unwrap().len(), 0);
+    }
+
+    let string = errors[0] - 1;
+    let b = errors[errors.len() - 1] + 1;
+    assert_eq!(store.next(string), Some(b));
+    assert_eq!(store.previous(b), Some(string));
+}
+
+#[test]
+fn only_trivia() {
+    let store = TokenStore::new("-- a");
+
+    let left = store.at_offset(4, Bias::Left).unwrap();
+    assert_eq!(left.location, TokenLocation::LeadingTrivia(0));
+    assert!(left.is_in_comment());
+
+    let right = store.at_offset(4, Bias::Right).unwrap();
+    assert_eq!(right.location, TokenLocation::Token);
+    assert_eq!(right.token.token_type, TokenType::EndOfFile);
+}
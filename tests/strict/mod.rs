@@ -0,0 +1,31 @@
+use lsp_types::Position;
+use luau_lexer::prelude::{Lexer, LexerConfig};
+
+#[test]
+fn tokenize_strict() {
+    let tokens = Lexer::tokenize_strict("local x = 1").unwrap();
+    assert_eq!(tokens, Lexer::new("local x = 1").collect_tokens());
+
+    let error = Lexer::tokenize_strict("local x = 'oops\n$").unwrap_err();
+    assert_eq!(error.start(), Position::new(0, 10));
+}
+
+#[test]
+fn max_errors() {
+    let code = "$ $ $ local";
+    let result = LexerConfig::new()
+        .with_max_errors(Some(2))
+        .build(code)
+        .try_collect_tokens();
+    assert_eq!(
+        result.unwrap_err().message(),
+        "Stopped lexing after reaching the maximum of 2 errors."
+    );
+
+    let tokens = LexerConfig::new()
+        .with_max_errors(Some(3))
+        .build(code)
+        .try_collect_tokens()
+        .unwrap();
+    assert_eq!(tokens.len(), 5);
+}
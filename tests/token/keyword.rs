@@ -0,0 +1,47 @@
+use luau_lexer::prelude::{Dialect, Keyword, PartialKeyword};
+
+#[test]
+fn keywords() {
+    for word in [
+        "local", "function", "if", "elseif", "then", "else", "while", "for", "in", "do", "break",
+        "return", "end", "repeat", "until", "nil",
+    ] {
+        let keyword = Keyword::try_from_str(word).unwrap();
+
+        assert_eq!(keyword.as_str(), word);
+        assert_eq!(keyword.to_string(), word);
+    }
+
+    assert_eq!(Keyword::try_from_str("goto"), None);
+    assert_eq!(
+        Keyword::try_from_str_in("goto", Dialect::Lua54),
+        Some(Keyword::Goto)
+    );
+}
+
+#[test]
+fn partial_keywords() {
+    for word in ["type", "continue", "export", "typeof"] {
+        assert_eq!(PartialKeyword::try_from_str(word).unwrap().as_str(), word);
+    }
+    for word in ["const", "close"] {
+        assert_eq!(PartialKeyword::try_from_str(word), None);
+        assert_eq!(
+            PartialKeyword::try_from_str_in(word, Dialect::Lua54)
+                .unwrap()
+                .as_str(),
+            word
+        );
+    }
+}
+
+#[test]
+fn non_keywords() {
+    // Words with the same length, first, and last characters as keywords.
+    for word in [
+        "lxcal", "fxnction", "eld", "nxl", "txpe", "rxturn", "Local", "_end", "",
+    ] {
+        assert_eq!(Keyword::try_from_str(word), None, "{word}");
+        assert_eq!(PartialKeyword::try_from_str(word), None, "{word}");
+    }
+}
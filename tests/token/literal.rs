@@ -48,6 +48,15 @@ generate_string_tests!(
     multi_line_4 => MultiLine("[[\nmulti-line test\n]]"),
     multi_line_5 => MultiLine("[==[\n\n\nmulti-line test]==]"),
     multi_line_6 => MultiLine("[==[multi-\nline]====\n test \n]==]"),
+    multi_line_7 => MultiLine("[=[multi-line test]]=]"),
+    multi_line_8 => MultiLine("[==[multi-line test]]]=]]==]"),
+    multi_line_9 => MultiLine("[==[ ]=] ]==]"),
+    multi_line_10 => MultiLine("[==[ ]x=] ]==]"),
+    multi_line_11 => MultiLine("[=[ ]]=]"),
+
+    single_quotes_escaped => SingleQuotes(r#"'single \' quotes \\'"#),
+    double_quotes_unicode => DoubleQuotes(r#""dóuble 🙂 quotes""#),
+    multi_line_unicode => MultiLine("[[multi-line 🙂\ntést]]"),
 );
 
 generate_string_tests!(
@@ -73,6 +82,33 @@ generate_string_tests!(
     #[should_panic] erroneous_multi_line_6 => MultiLine("[==[multi-\nline]====\n test \n]=]"),
 );
 
+#[test]
+fn unterminated_multi_line() {
+    for code in [
+        "[[unterminated",
+        "[==[ ]=] ]=]",
+        "[=[ ]] ]==]",
+        "[==[ ]==",
+        "[=[",
+    ] {
+        let tokens = Lexer::new(code).collect_tokens();
+
+        assert_eq!(
+            tokens[0].token_type,
+            TokenType::Literal(Literal::String(LuauString::MultiLine(code.into()))),
+            "{code:?}"
+        );
+        assert!(
+            matches!(
+                &tokens[1].token_type,
+                TokenType::Error(error) if error.message() == "Malformed multi-line string."
+            ),
+            "{code:?}: {:?}",
+            tokens[1]
+        );
+    }
+}
+
 macro_rules! generate_number_tests {
     ($( $(#[$meta: meta])? $fn_name: ident => $enum: ident ($str: literal) ),* $(,)?) => {
         $(
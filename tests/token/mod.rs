@@ -1 +1,4 @@
+mod keyword;
 mod literal;
+mod position;
+mod trivia;
@@ -0,0 +1,41 @@
+use lsp_types::Position;
+use luau_lexer::prelude::{Lexer, TokenType};
+
+#[test]
+fn end_excludes_trailing_trivia() {
+    let mut lexer = Lexer::new("local  x -- comment\n");
+
+    let local = lexer.next_token();
+    assert_eq!(local.start, Position::new(0, 0));
+    assert_eq!(local.end, Position::new(0, 5));
+
+    let x = lexer.next_token();
+    assert_eq!(x.start, Position::new(0, 7));
+    assert_eq!(x.end, Position::new(0, 8));
+
+    let end_of_file = lexer.next_token();
+    assert_eq!(end_of_file, TokenType::EndOfFile);
+    assert_eq!(end_of_file.start, Position::new(1, 0));
+}
+
+#[test]
+fn queued_errors_start_at_lexer_position() {
+    let mut lexer = Lexer::new("x = 'oops\ny");
+    let mut errors = Vec::new();
+
+    loop {
+        let token = lexer.next_token();
+        match &token.token_type {
+            TokenType::Error(error) => errors.push((token.start, token.end, error.start())),
+            TokenType::EndOfFile => break,
+            _ => (),
+        }
+    }
+
+    assert!(!errors.is_empty());
+    for (start, end, error_start) in errors {
+        assert_eq!(start, Position::new(1, 0));
+        assert_eq!(end, Position::new(1, 0));
+        assert_eq!(error_start, Position::new(0, 4));
+    }
+}
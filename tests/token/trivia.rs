@@ -0,0 +1,59 @@
+use lsp_types::{Position, Range};
+use luau_lexer::prelude::{Comment, LexerConfig, TriviaType};
+
+#[test]
+fn positions() {
+    let code = "local x -- née\n\t--[[ a\nb ]] = 1";
+    let tokens = LexerConfig::new()
+        .with_tab_width(4)
+        .build(code)
+        .collect_tokens();
+
+    let trivia = &tokens[1].trailing_trivia;
+    assert_eq!(trivia.len(), 5);
+    assert_eq!(tokens[2].leading_trivia, *trivia);
+
+    let comment = &trivia[1];
+    assert_eq!(
+        comment.trivia_type,
+        TriviaType::Comment(Comment::SingleLine("-- née".into()))
+    );
+    assert_eq!(
+        (comment.start, comment.end),
+        (Position::new(0, 8), Position::new(0, 14))
+    );
+    assert_eq!(&code[comment.start_offset..comment.end_offset], "-- née");
+
+    let block = &trivia[3];
+    assert!(block.is_comment());
+    assert_eq!(
+        (block.start, block.end),
+        (Position::new(1, 4), Position::new(2, 4))
+    );
+    assert_eq!(&code[block.start_offset..block.end_offset], "--[[ a\nb ]]");
+
+    for trivia in trivia {
+        assert_eq!(
+            &code[trivia.start_offset..trivia.end_offset],
+            trivia.as_str()
+        );
+    }
+}
+
+#[test]
+fn full_range() {
+    let tokens = LexerConfig::new().build("  x -- y\nz").collect_tokens();
+
+    assert_eq!(
+        tokens[0].full_range(),
+        Range::new(Position::new(0, 0), Position::new(1, 0))
+    );
+    assert_eq!(
+        tokens[1].full_range(),
+        Range::new(Position::new(0, 3), Position::new(1, 1))
+    );
+    assert_eq!(
+        tokens[2].full_range(),
+        Range::new(Position::new(1, 1), Position::new(1, 1))
+    );
+}
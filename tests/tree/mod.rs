@@ -0,0 +1,119 @@
+use luau_lexer::prelude::{GroupKind, Lexer, Token, TokenTree};
+
+fn tokens(code: &str) -> Vec<Token> {
+    Lexer::new(code).collect_tokens()
+}
+
+/// The opening and closing text of each group, with its depth.
+fn groups(tree: &TokenTree) -> Vec<(String, Option<String>, usize)> {
+    tree.groups()
+        .into_iter()
+        .map(|group| {
+            (
+                group.open.token_type.try_as_string().unwrap_or_default(),
+                group
+                    .close
+                    .and_then(|close| close.token_type.try_as_string()),
+                group.depth,
+            )
+        })
+        .collect()
+}
+
+fn group(open: &str, close: &str, depth: usize) -> (String, Option<String>, usize) {
+    (open.to_string(), Some(close.to_string()), depth)
+}
+
+#[test]
+fn nesting() {
+    let tokens = tokens("local function foo(a) return { a[1], (a) } end");
+    let tree = TokenTree::new(&tokens);
+
+    assert!(tree.errors.is_empty());
+    assert_eq!(
+        groups(&tree),
+        [
+            group("function", "end", 0),
+            group("(", ")", 1),
+            group("{", "}", 1),
+            group("[", "]", 2),
+            group("(", ")", 2),
+        ]
+    );
+    assert_eq!(tree.groups()[2].kind, GroupKind::Braces);
+}
+
+#[test]
+fn blocks() {
+    let tokens = tokens(
+        "while a do repeat b() until c end
+        if a then x() elseif b then y() else z() end
+        for i = 1, 2 do end",
+    );
+    let tree = TokenTree::new(&tokens);
+
+    assert!(tree.errors.is_empty());
+    assert_eq!(
+        groups(&tree),
+        [
+            group("do", "end", 0),
+            group("repeat", "until", 1),
+            group("(", ")", 2),
+            group("then", "elseif", 0),
+            group("(", ")", 1),
+            group("then", "else", 0),
+            group("(", ")", 1),
+            group("else", "end", 0),
+            group("(", ")", 1),
+            group("do", "end", 0),
+        ]
+    );
+}
+
+#[test]
+fn if_expressions() {
+    let tokens = tokens(
+        "local x = if a then b elseif c then d else e
+        if if a then b else c then
+            return if a then b else c
+        end",
+    );
+    let tree = TokenTree::new(&tokens);
+
+    assert!(tree.errors.is_empty());
+    assert_eq!(groups(&tree), [group("then", "end", 0)]);
+}
+
+#[test]
+fn mismatched_closer() {
+    let tokens = tokens("local t = { (1 }");
+    let tree = TokenTree::new(&tokens);
+
+    assert_eq!(groups(&tree)[0], group("{", "}", 0));
+    assert_eq!(groups(&tree)[1], ("(".to_string(), None, 1));
+
+    assert_eq!(tree.errors.len(), 1);
+    assert_eq!(tree.errors[0].start(), tokens[4].start);
+    assert_eq!(tree.errors[0].end(), Some(tokens[6].end));
+}
+
+#[test]
+fn missing_end() {
+    let tokens = tokens("function foo()\n    if a then\n    end\n");
+    let tree = TokenTree::new(&tokens);
+
+    assert_eq!(tree.errors.len(), 1);
+    assert_eq!(tree.errors[0].start(), tokens[0].start);
+    assert_eq!(tree.errors[0].end(), Some(tokens.last().unwrap().end));
+    assert!(tree.errors[0].message().contains("`end`"));
+}
+
+#[test]
+fn stray_closer() {
+    let tokens = tokens("a() end )");
+    let tree = TokenTree::new(&tokens);
+
+    assert_eq!(tree.errors.len(), 2);
+    assert_eq!(tree.errors[0].start(), tokens[3].start);
+    assert_eq!(tree.errors[1].start(), tokens[4].start);
+}
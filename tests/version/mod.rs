@@ -0,0 +1,167 @@
+use luau_lexer::prelude::{
+    Error, Lexer, LexerConfig, LuauFeature, LuauFeatures, LuauVersion, Severity, TokenType,
+};
+
+fn lexer_errors(mut lexer: Lexer) -> Vec<Error> {
+    lexer
+        .collect_tokens()
+        .into_iter()
+        .filter_map(|token| match token.token_type {
+            TokenType::Error(error) => Some(error),
+            _ => None,
+        })
+        .collect()
+}
+
+fn errors(code: &str, features: LuauFeatures, severity: Severity) -> Vec<Error> {
+    lexer_errors(
+        LexerConfig::new()
+            .with_luau_features(features)
+            .with_unsupported_syntax(severity)
+            .build(code),
+    )
+}
+
+const CODE: &str = "
+@native
+type function foo() end
+type Bar = number
+local a = `{b}` // 2
+a += 1
+a //= 2
+a = a == 1
+";
+
+#[test]
+fn all_features() {
+    assert!(errors(CODE, LuauFeatures::ALL, Severity::Error).is_empty());
+}
+
+#[test]
+fn no_features() {
+    let errors = errors(CODE, LuauFeatures::NONE, Severity::Error);
+    let messages = errors.iter().map(Error::message).collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        [
+            "The targeted luau version doesn't support attributes.",
+            "The targeted luau version doesn't support type functions.",
+            "The targeted luau version doesn't support string interpolation.",
+            "The targeted luau version doesn't support floor division.",
+            "The targeted luau version doesn't support compound assignments.",
+            "The targeted luau version doesn't support compound assignments.",
+            "The targeted luau version doesn't support floor division.",
+        ]
+    );
+    assert!(
+        errors
+            .iter()
+            .all(|error| error.severity() == Severity::Error)
+    );
+}
+
+#[test]
+fn single_feature() {
+    let features = LuauFeatures::ALL.without(LuauFeature::FloorDivision);
+    let errors = errors(CODE, features, Severity::Warning);
+
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(Error::is_warning));
+    assert_eq!(errors[0].start().line, 4);
+    assert_eq!(errors[1].start().line, 6);
+}
+
+#[test]
+fn features_set() {
+    let features = [LuauFeature::Attributes, LuauFeature::FloorDivision]
+        .into_iter()
+        .collect::<LuauFeatures>();
+
+    assert!(features.contains(LuauFeature::Attributes));
+    assert!(!features.contains(LuauFeature::TypeFunctions));
+    assert_eq!(
+        features
+            .without(LuauFeature::Attributes)
+            .with(LuauFeature::Attributes),
+        features
+    );
+}
+
+#[test]
+fn older_version() {
+    let code = "local a = `{b}`\na //= 2\n";
+
+    let errors = lexer_errors(
+        LexerConfig::new()
+            .with_luau_version(LuauVersion::StringInterpolation)
+            .build(code),
+    );
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message(),
+        "The targeted luau version doesn't support floor division."
+    );
+    assert_eq!(errors[0].start().line, 1);
+    assert_eq!(errors[0].severity(), Severity::Error);
+
+    let errors = lexer_errors(
+        LexerConfig::new()
+            .with_luau_version(LuauVersion::CompoundAssignment)
+            .with_unsupported_syntax(Severity::Warning)
+            .build(code),
+    );
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(Error::is_warning));
+
+    assert!(
+        lexer_errors(
+            LexerConfig::new()
+                .with_luau_version(LuauVersion::FloorDivision)
+                .build(code),
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn versions() {
+    let versions = [
+        LuauVersion::CompoundAssignment,
+        LuauVersion::StringInterpolation,
+        LuauVersion::FloorDivision,
+        LuauVersion::Attributes,
+        LuauVersion::TypeFunctions,
+    ];
+
+    assert_eq!(LuauVersion::default(), LuauVersion::LATEST);
+    assert_eq!(LuauVersion::LATEST.features(), LuauFeatures::ALL);
+    assert!(
+        !LuauVersion::Attributes
+            .features()
+            .contains(LuauFeature::TypeFunctions)
+    );
+
+    let features = [
+        LuauFeature::CompoundAssignment,
+        LuauFeature::StringInterpolation,
+        LuauFeature::FloorDivision,
+        LuauFeature::Attributes,
+        LuauFeature::TypeFunctions,
+    ];
+
+    // Each version supports everything the previous ones do, plus one feature.
+    for pair in versions.windows(2) {
+        let (older, newer) = (pair[0].features(), pair[1].features());
+        let added = features
+            .iter()
+            .filter(|feature| newer.contains(**feature) && !older.contains(**feature));
+
+        assert_eq!(added.count(), 1);
+        assert!(
+            features
+                .iter()
+                .all(|feature| !older.contains(*feature) || newer.contains(*feature))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use luau_lexer::prelude::{Lexer, WorkspaceFile, WorkspaceLexer};
+use std::{env, fs, ops::ControlFlow, process, sync::Mutex};
+
+/// Create the passed number of files, each with a different amount of code.
+fn files(count: usize) -> Vec<WorkspaceFile> {
+    (0..count)
+        .map(|i| {
+            let code = format!("local x{i} = {i}\n").repeat(i % 7 + 1) + "print('oops\n";
+            WorkspaceFile::from((format!("file{i}.luau"), code))
+        })
+        .collect()
+}
+
+#[test]
+fn ordered() {
+    let files = files(50);
+    let workspace = WorkspaceLexer::new().with_threads(4).lex(&files);
+
+    assert!(!workspace.is_cancelled);
+    assert_eq!(workspace.files.len(), files.len());
+
+    for (lexed, file) in workspace.files.iter().zip(&files) {
+        let WorkspaceFile::Code { id, code } = file else {
+            unreachable!();
+        };
+
+        assert_eq!(&lexed.id, id);
+        assert_eq!(lexed.tokens, Lexer::new(code).collect_tokens());
+        assert!(!lexed.errors.is_empty());
+        assert!(lexed.read_error.is_none());
+    }
+}
+
+#[test]
+fn paths() {
+    let directory = env::temp_dir().join(format!("luau-lexer-workspace-{}", process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join("main.luau"), "return 1").unwrap();
+
+    let files = [
+        WorkspaceFile::from(directory.join("main.luau")),
+        WorkspaceFile::from(directory.join("missing.luau")),
+    ];
+    let workspace = WorkspaceLexer::new().lex(&files);
+    fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(workspace.files[0].id, files[0].id());
+    assert_eq!(workspace.files[0].tokens.len(), 3);
+    assert!(workspace.files[0].read_error.is_none());
+
+    assert!(workspace.files[1].tokens.is_empty());
+    assert!(workspace.files[1].read_error.is_some());
+}
+
+#[test]
+fn progress() {
+    let files = files(20);
+    let indices = Mutex::new(Vec::new());
+
+    let workspace = WorkspaceLexer::new()
+        .with_threads(3)
+        .lex_with_progress(&files, |progress| {
+            assert_eq!(progress.total, files.len());
+            assert_eq!(progress.file.id, files[progress.index].id());
+            indices
+                .lock()
+                .unwrap()
+                .push((progress.completed, progress.index));
+
+            ControlFlow::Continue(())
+        });
+
+    let mut indices = indices.into_inner().unwrap();
+    indices.sort_unstable();
+
+    assert_eq!(workspace.files.len(), files.len());
+    assert!(
+        indices
+            .iter()
+            .map(|(completed, _)| *completed)
+            .eq(1..=files.len())
+    );
+
+    let mut indices = indices
+        .into_iter()
+        .map(|(_, index)| index)
+        .collect::<Vec<_>>();
+    indices.sort_unstable();
+    assert!(indices.into_iter().eq(0..files.len()));
+}
+
+#[test]
+fn cancelled() {
+    let files = files(20);
+    let workspace = WorkspaceLexer::new()
+        .with_threads(1)
+        .lex_with_progress(&files, |progress| {
+            if progress.completed == 5 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+    assert!(workspace.is_cancelled);
+    assert!(
+        workspace
+            .files
+            .iter()
+            .map(|file| file.id.clone())
+            .eq(files[..5].iter().map(WorkspaceFile::id))
+    );
+}